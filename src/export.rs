@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use petgraph::graph::NodeIndex;
+
+use crate::node::Node;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Format {
+    Json,
+    Csv,
+    GeoJson,
+    Kml,
+    Gpx,
+}
+
+impl Format {
+    pub const ALL: [Format; 5] = [
+        Format::Json,
+        Format::Csv,
+        Format::GeoJson,
+        Format::Kml,
+        Format::Gpx,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::GeoJson => "geojson",
+            Format::Kml => "kml",
+            Format::Gpx => "gpx",
+        }
+    }
+
+    /// The format a file name's extension asks for.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        Format::ALL
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+
+    /// Map formats place every city by its latitude and longitude.
+    fn needs_coordinates(&self) -> bool {
+        matches!(self, Format::GeoJson | Format::Kml | Format::Gpx)
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Json => write!(f, "JSON"),
+            Format::Csv => write!(f, "CSV"),
+            Format::GeoJson => write!(f, "GeoJSON"),
+            Format::Kml => write!(f, "KML"),
+            Format::Gpx => write!(f, "GPX"),
+        }
+    }
+}
+
+/// Routes found by a solver with the cities and distances they pass, ready to be written
+/// out. A single tour is one route.
+pub struct Solution<'a> {
+    pub cost: u32,
+    pub routes: &'a [Vec<usize>],
+    pub nodes: &'a HashMap<NodeIndex, Node>,
+    pub matrix: &'a [Vec<u32>],
+}
+
+impl Solution<'_> {
+    pub fn save(&self, path: &Path, format: Format) -> io::Result<()> {
+        fs::write(path, self.render(format)?)
+    }
+
+    /// The solution in `format`. Map formats fail when a city on a route has no
+    /// coordinates.
+    pub fn render(&self, format: Format) -> io::Result<String> {
+        if format.needs_coordinates()
+            && let Some(city) = self
+                .routes
+                .iter()
+                .flatten()
+                .find(|&&city| self.node(city).position.is_none())
+        {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} has no coordinates; add lat and lon columns to the cities file",
+                    self.node(*city).name
+                ),
+            ));
+        }
+
+        Ok(match format {
+            Format::Json => self.json(),
+            Format::Csv => self.csv(),
+            Format::GeoJson => self.geojson(),
+            Format::Kml => self.kml(),
+            Format::Gpx => self.gpx(),
+        })
+    }
+
+    fn node(&self, city: usize) -> &Node {
+        &self.nodes[&NodeIndex::new(city)]
+    }
+
+    /// Latitude and longitude; only called once `render` checked that they exist.
+    fn position(&self, city: usize) -> (f64, f64) {
+        self.node(city).position.unwrap_or_default()
+    }
+
+    fn length(&self, route: &[usize]) -> u32 {
+        route
+            .windows(2)
+            .map(|pair| self.matrix[pair[0]][pair[1]])
+            .sum()
+    }
+
+    /// Cities carry their index, which is their place among the dataset's cities sorted
+    /// by name, so it means the same city in every run on the same dataset.
+    fn json(&self) -> String {
+        let routes: Vec<String> = self
+            .routes
+            .iter()
+            .map(|route| {
+                let cities: Vec<String> = route
+                    .iter()
+                    .map(|&city| {
+                        format!(
+                            "{{\"index\": {city}, \"name\": {}}}",
+                            json_string(&self.node(city).name)
+                        )
+                    })
+                    .collect();
+
+                let legs: Vec<String> = route
+                    .windows(2)
+                    .map(|pair| {
+                        format!(
+                            "{{\"from\": {}, \"to\": {}, \"distance\": {}}}",
+                            json_string(&self.node(pair[0]).name),
+                            json_string(&self.node(pair[1]).name),
+                            self.matrix[pair[0]][pair[1]]
+                        )
+                    })
+                    .collect();
+
+                format!(
+                    "    {{\n      \"length\": {},\n      \"cities\": [\n        {}\n      ],\n      \
+                     \"legs\": [\n        {}\n      ]\n    }}",
+                    self.length(route),
+                    cities.join(",\n        "),
+                    legs.join(",\n        ")
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\n  \"cost\": {},\n  \"routes\": [\n{}\n  ]\n}}\n",
+            self.cost,
+            routes.join(",\n")
+        )
+    }
+
+    /// One row per stop, with the city's index as in `json`, the leg that reaches it and
+    /// the distance so far.
+    fn csv(&self) -> String {
+        let mut lines = vec!["route,stop,index,city,country,leg,total".to_string()];
+
+        self.routes.iter().enumerate().for_each(|(number, route)| {
+            let mut total = 0;
+
+            route.iter().enumerate().for_each(|(stop, &city)| {
+                let leg = match stop {
+                    0 => 0,
+                    _ => self.matrix[route[stop - 1]][city],
+                };
+                total += leg;
+
+                let node = self.node(city);
+                let name = node.name.split(',').next().unwrap_or_default();
+
+                lines.push(format!(
+                    "{},{stop},{city},{},{},{leg},{total}",
+                    number + 1,
+                    csv_field(name),
+                    csv_field(&node.country)
+                ));
+            });
+        });
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /// A LineString per route and a Point per city, in longitude, latitude order.
+    fn geojson(&self) -> String {
+        let coordinates = |city: usize| {
+            let (lat, lon) = self.position(city);
+            format!("[{lon}, {lat}]")
+        };
+
+        let lines = self.routes.iter().enumerate().map(|(number, route)| {
+            let points: Vec<String> = route.iter().map(|&city| coordinates(city)).collect();
+
+            format!(
+                "    {{\"type\": \"Feature\", \"properties\": {{\"route\": {}, \"length\": {}}}, \
+                 \"geometry\": {{\"type\": \"LineString\", \"coordinates\": [{}]}}}}",
+                number + 1,
+                self.length(route),
+                points.join(", ")
+            )
+        });
+
+        let points = self.stops().map(|(number, stop, city)| {
+            format!(
+                "    {{\"type\": \"Feature\", \"properties\": {{\"name\": {}, \"route\": {}, \
+                 \"stop\": {stop}}}, \"geometry\": {{\"type\": \"Point\", \"coordinates\": {}}}}}",
+                json_string(&self.node(city).name),
+                number + 1,
+                coordinates(city)
+            )
+        });
+
+        let features: Vec<String> = lines.chain(points).collect();
+
+        format!(
+            "{{\n  \"type\": \"FeatureCollection\",\n  \"features\": [\n{}\n  ]\n}}\n",
+            features.join(",\n")
+        )
+    }
+
+    fn kml(&self) -> String {
+        let coordinates = |city: usize| {
+            let (lat, lon) = self.position(city);
+            format!("{lon},{lat},0")
+        };
+
+        let mut placemarks: Vec<String> = self
+            .routes
+            .iter()
+            .enumerate()
+            .map(|(number, route)| {
+                let points: Vec<String> = route.iter().map(|&city| coordinates(city)).collect();
+
+                format!(
+                    "    <Placemark>\n      <name>Route {} ({} km)</name>\n      <LineString>\n        \
+                     <tessellate>1</tessellate>\n        <coordinates>{}</coordinates>\n      \
+                     </LineString>\n    </Placemark>",
+                    number + 1,
+                    self.length(route),
+                    points.join(" ")
+                )
+            })
+            .collect();
+
+        placemarks.extend(self.stops().map(|(_, stop, city)| {
+            format!(
+                "    <Placemark>\n      <name>{}. {}</name>\n      <Point><coordinates>{}</coordinates></Point>\n    \
+                 </Placemark>",
+                stop + 1,
+                xml_text(&self.node(city).name),
+                coordinates(city)
+            )
+        }));
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n  \
+             <Document>\n    <name>Tour of {} km</name>\n{}\n  </Document>\n</kml>\n",
+            self.cost,
+            placemarks.join("\n")
+        )
+    }
+
+    /// A GPX route per solver route, with a route point per stop.
+    fn gpx(&self) -> String {
+        let routes: Vec<String> = self
+            .routes
+            .iter()
+            .enumerate()
+            .map(|(number, route)| {
+                let points: Vec<String> = route
+                    .iter()
+                    .map(|&city| {
+                        let (lat, lon) = self.position(city);
+
+                        format!(
+                            "    <rtept lat=\"{lat}\" lon=\"{lon}\"><name>{}</name></rtept>",
+                            xml_text(&self.node(city).name)
+                        )
+                    })
+                    .collect();
+
+                format!(
+                    "  <rte>\n    <name>Route {} ({} km)</name>\n{}\n  </rte>",
+                    number + 1,
+                    self.length(route),
+                    points.join("\n")
+                )
+            })
+            .collect();
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gpx version=\"1.1\" creator=\"pathfinder\" \
+             xmlns=\"http://www.topografix.com/GPX/1/1\">\n{}\n</gpx>\n",
+            routes.join("\n")
+        )
+    }
+
+    /// Every stop of every route as (route, stop, city), leaving out the return to the
+    /// start of a closed route so each city is marked once.
+    fn stops(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.routes.iter().enumerate().flat_map(|(number, route)| {
+            let closed = route.len() > 1 && route.first() == route.last();
+            let stops = if closed {
+                &route[..route.len() - 1]
+            } else {
+                &route[..]
+            };
+
+            stops
+                .iter()
+                .enumerate()
+                .map(move |(stop, &city)| (number, stop, city))
+        })
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let escaped: String = text
+        .chars()
+        .flat_map(|c| match c {
+            '"' => vec!['\\', '"'],
+            '\\' => vec!['\\', '\\'],
+            '\n' => vec!['\\', 'n'],
+            c => vec![c],
+        })
+        .collect();
+
+    format!("\"{escaped}\"")
+}
+
+/// Quotes a field that holds a comma or a quote.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn xml_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::fmt;
+use std::path::Path;
+
+use egui::{Color32, Pos2, Vec2};
+use petgraph::EdgeType;
+use petgraph::dot::{Config, Dot};
+use petgraph::graph::NodeIndex;
+use petgraph::stable_graph::{EdgeReference, StableGraph};
+use petgraph::visit::EdgeRef;
+
+use crate::export::xml_text;
+use crate::node::Node;
+use crate::route_edge::ROUTE_COLOURS;
+
+const NODE_RADIUS: f32 = 5.0;
+const MARGIN: f32 = 80.0;
+/// Width of a drawing laid out from coordinates or on a circle.
+const WIDTH: f32 = 1000.0;
+const BACKGROUND: &str = "#1b1b1b";
+const FOREGROUND: &str = "#d2d2d2";
+const EDGE: &str = "#8c8c8c";
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Figure {
+    Dot,
+    Svg,
+}
+
+impl Figure {
+    pub const ALL: [Figure; 2] = [Figure::Dot, Figure::Svg];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Figure::Dot => "dot",
+            Figure::Svg => "svg",
+        }
+    }
+
+    /// The figure a file name's extension asks for.
+    pub fn from_path(path: &Path) -> Option<Figure> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        Figure::ALL
+            .into_iter()
+            .find(|figure| figure.extension() == extension)
+    }
+}
+
+impl fmt::Display for Figure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Figure::Dot => write!(f, "Graphviz DOT"),
+            Figure::Svg => write!(f, "SVG"),
+        }
+    }
+}
+
+/// Graphviz source for the whole instance: every edge is labelled with its distance, and
+/// the edges of each route are drawn thick in the route's colour.
+pub fn dot<Ty: EdgeType>(
+    graph: &StableGraph<String, (), Ty>,
+    matrix: &[Vec<u32>],
+    routes: &[Vec<usize>],
+) -> String {
+    let route_of = |from: usize, to: usize| {
+        routes.iter().position(|route| {
+            route.windows(2).any(|pair| {
+                (pair[0], pair[1]) == (from, to)
+                    || (!Ty::is_directed() && (pair[1], pair[0]) == (from, to))
+            })
+        })
+    };
+
+    let edge = |_, edge: EdgeReference<'_, ()>| {
+        let (from, to) = (edge.source().index(), edge.target().index());
+        let label = format!("label=\"{}\"", matrix[from][to]);
+
+        match route_of(from, to) {
+            Some(route) => format!(
+                "{label} color=\"{}\" penwidth=3",
+                ROUTE_COLOURS[route % ROUTE_COLOURS.len()].1
+            ),
+            None => format!("{label} color=\"gray\""),
+        }
+    };
+
+    let node =
+        |_, (_, name): (NodeIndex, &String)| format!("label=\"{}\"", name.replace('"', "\\\""));
+
+    // Edges carry no weight to display, so labels come from the attribute getters.
+    format!(
+        "{:?}",
+        Dot::with_attr_getters(
+            graph,
+            &[Config::EdgeNoLabel, Config::NodeNoLabel],
+            &edge,
+            &node
+        )
+    )
+}
+
+/// What the graph view shows, ready to be drawn as SVG.
+pub struct Drawing {
+    pub names: Vec<String>,
+    pub positions: Vec<Pos2>,
+    /// Edges with their label and, for an edge drawn in a route's colour, that colour.
+    pub edges: Vec<(usize, usize, String, Option<Color32>)>,
+    pub directed: bool,
+}
+
+impl Drawing {
+    /// Only the edges of the routes, coloured when there is more than one route, as the
+    /// graph view shows them after a solve.
+    pub fn routes(
+        nodes: &HashMap<NodeIndex, Node>,
+        matrix: &[Vec<u32>],
+        routes: &[Vec<usize>],
+        directed: bool,
+    ) -> Self {
+        let edges = routes
+            .iter()
+            .enumerate()
+            .flat_map(|(number, route)| {
+                let colour =
+                    (routes.len() > 1).then_some(ROUTE_COLOURS[number % ROUTE_COLOURS.len()].0);
+
+                route.windows(2).map(move |pair| {
+                    (
+                        pair[0],
+                        pair[1],
+                        matrix[pair[0]][pair[1]].to_string(),
+                        colour,
+                    )
+                })
+            })
+            .collect();
+
+        Self {
+            names: (0..nodes.len())
+                .map(|city| nodes[&NodeIndex::new(city)].name.clone())
+                .collect(),
+            positions: layout(nodes),
+            edges,
+            directed,
+        }
+    }
+
+    pub fn svg(&self) -> String {
+        let (min, max) = self.positions.iter().fold(
+            (Pos2::new(f32::MAX, f32::MAX), Pos2::new(f32::MIN, f32::MIN)),
+            |(min, max), &position| (min.min(position), max.max(position)),
+        );
+        let origin = min - Vec2::splat(MARGIN);
+        let size = (max - min) + Vec2::splat(2.0 * MARGIN);
+
+        let mut lines = vec![
+            format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" \
+                 width=\"{}\" height=\"{}\" font-family=\"sans-serif\">",
+                origin.x, origin.y, size.x, size.y, size.x, size.y
+            ),
+            format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{BACKGROUND}\"/>",
+                origin.x, origin.y, size.x, size.y
+            ),
+        ];
+
+        self.edges.iter().for_each(|(from, to, label, colour)| {
+            let (start, end) = (self.positions[*from], self.positions[*to]);
+            let direction = (end - start).normalized();
+            let (start, end) = (
+                start + direction * NODE_RADIUS,
+                end - direction * NODE_RADIUS,
+            );
+
+            let (stroke, width) = match colour {
+                Some(colour) => (hex(*colour), 3.0),
+                None => (EDGE.to_string(), 1.5),
+            };
+
+            lines.push(format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{stroke}\" \
+                 stroke-width=\"{width}\"/>",
+                start.x, start.y, end.x, end.y
+            ));
+
+            if self.directed {
+                let (back, side) = (end - direction * 10.0, direction.rot90() * 4.0);
+                lines.push(format!(
+                    "  <polygon points=\"{},{} {},{} {},{}\" fill=\"{stroke}\"/>",
+                    end.x,
+                    end.y,
+                    (back + side).x,
+                    (back + side).y,
+                    (back - side).x,
+                    (back - side).y
+                ));
+            }
+
+            let middle = start + (end - start) / 2.0;
+            lines.push(format!(
+                "  <text x=\"{}\" y=\"{}\" font-size=\"10\" fill=\"{FOREGROUND}\" \
+                 text-anchor=\"middle\">{}</text>",
+                middle.x,
+                middle.y - 3.0,
+                xml_text(label)
+            ));
+        });
+
+        self.positions
+            .iter()
+            .zip(&self.names)
+            .for_each(|(position, name)| {
+                lines.push(format!(
+                    "  <circle cx=\"{}\" cy=\"{}\" r=\"{NODE_RADIUS}\" fill=\"{FOREGROUND}\"/>",
+                    position.x, position.y
+                ));
+                lines.push(format!(
+                    "  <text x=\"{}\" y=\"{}\" font-size=\"12\" fill=\"{FOREGROUND}\" \
+                     text-anchor=\"middle\">{}</text>",
+                    position.x,
+                    position.y - 2.0 * NODE_RADIUS,
+                    xml_text(name)
+                ));
+            });
+
+        lines.push("</svg>".to_string());
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Places the cities by their coordinates when every city has them, projected so that
+/// distances near the mean latitude keep their proportions, and evenly on a circle
+/// otherwise.
+fn layout(nodes: &HashMap<NodeIndex, Node>) -> Vec<Pos2> {
+    let positions: Option<Vec<(f64, f64)>> = (0..nodes.len())
+        .map(|city| nodes[&NodeIndex::new(city)].position)
+        .collect();
+
+    let Some(positions) = positions.filter(|positions| !positions.is_empty()) else {
+        let radius = WIDTH / 2.0;
+
+        return (0..nodes.len())
+            .map(|city| {
+                let angle = TAU * city as f32 / nodes.len() as f32;
+                Pos2::new(radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+    };
+
+    let mean = positions.iter().map(|position| position.0).sum::<f64>() / positions.len() as f64;
+    let squeeze = mean.to_radians().cos();
+
+    let projected: Vec<(f64, f64)> = positions
+        .iter()
+        .map(|&(lat, lon)| (lon * squeeze, -lat))
+        .collect();
+
+    let (min_x, max_x) = projected
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), p| {
+            (min.min(p.0), max.max(p.0))
+        });
+    let scale = WIDTH as f64 / (max_x - min_x).max(f64::EPSILON);
+
+    projected
+        .iter()
+        .map(|&(x, y)| Pos2::new((x * scale) as f32, (y * scale) as f32))
+        .collect()
+}
+
+fn hex(colour: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", colour.r(), colour.g(), colour.b())
+}
@@ -0,0 +1,376 @@
+use std::collections::HashSet;
+use std::f64::consts::TAU;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::{Rng, SeedableRng};
+
+/// Syllables city and country names are made of.
+const SYLLABLES: [&str; 24] = [
+    "ba", "ber", "ca", "dor", "el", "fen", "gar", "ha", "is", "ka", "lin", "ma", "nor", "o", "pol",
+    "ra", "sen", "ta", "ul", "va", "wes", "ya", "zan", "vi",
+];
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Kind {
+    /// Cities spread uniformly over a square.
+    Uniform,
+    /// Cities gathered in normally distributed clusters around random centres.
+    Clustered,
+    /// Cities on the points of a square grid, filled row by row.
+    Grid,
+    /// Cities evenly spaced on a circle, whose optimal tour is the polygon.
+    Circle,
+    /// Random distances closed under shortest paths, so they keep the triangle
+    /// inequality without coming from points in a plane.
+    RandomMetric,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 5] = [
+        Kind::Uniform,
+        Kind::Clustered,
+        Kind::Grid,
+        Kind::Circle,
+        Kind::RandomMetric,
+    ];
+
+    /// The kind named on the command line, such as `clustered` or `random-metric`.
+    pub fn from_name(name: &str) -> Option<Kind> {
+        Kind::ALL
+            .into_iter()
+            .find(|kind| kind.to_string().to_lowercase().replace(' ', "-") == name.to_lowercase())
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Uniform => write!(f, "Uniform"),
+            Kind::Clustered => write!(f, "Clustered"),
+            Kind::Grid => write!(f, "Grid"),
+            Kind::Circle => write!(f, "Circle"),
+            Kind::RandomMetric => write!(f, "Random metric"),
+        }
+    }
+}
+
+/// Builds random instances for benchmarking. Euclidean kinds place the cities in a square
+/// `width` km wide and round distances to the nearest km, as TSPLIB's `EUC_2D` does.
+pub struct Generator {
+    pub kind: Kind,
+    pub cities: usize,
+    /// Seed for a reproducible instance; a random seed is drawn when unset.
+    pub seed: Option<u64>,
+    pub width: u32,
+    /// Countries the cities are grouped in: the clusters of a clustered instance, and
+    /// otherwise the cities nearest to as many random capitals.
+    pub regions: usize,
+}
+
+impl Generator {
+    pub fn new(kind: Kind, cities: usize) -> Self {
+        Self {
+            kind,
+            cities,
+            seed: None,
+            width: 2000,
+            regions: 8,
+        }
+    }
+
+    pub fn generate(&self) -> Instance {
+        let seed = self.seed.unwrap_or_else(|| rand::rng().random());
+        let mut rng = StdRng::seed_from_u64(seed);
+        let regions = self.regions.clamp(1, self.cities.max(1));
+
+        let (coordinates, clusters) = match self.kind {
+            Kind::Uniform => (Some(self.uniform(&mut rng)), None),
+            Kind::Clustered => {
+                let (points, clusters) = self.clustered(&mut rng, regions);
+                (Some(points), Some(clusters))
+            }
+            Kind::Grid => (Some(self.grid()), None),
+            Kind::Circle => (Some(self.circle()), None),
+            Kind::RandomMetric => (None, None),
+        };
+
+        let matrix = match &coordinates {
+            Some(points) => euclidean(points),
+            None => self.random_metric(&mut rng),
+        };
+
+        let region_of = clusters.unwrap_or_else(|| nearest_capital(&matrix, &mut rng, regions));
+
+        let mut names = Names::default();
+        let countries: Vec<String> = (0..regions).map(|_| names.next(&mut rng)).collect();
+        let names = (0..self.cities)
+            .map(|city| (names.next(&mut rng), countries[region_of[city]].clone()))
+            .collect();
+
+        Instance {
+            kind: self.kind,
+            seed,
+            names,
+            coordinates,
+            matrix,
+        }
+    }
+
+    fn uniform(&self, rng: &mut StdRng) -> Vec<(f64, f64)> {
+        let width = self.width as f64;
+
+        (0..self.cities)
+            .map(|_| (rng.random_range(0.0..=width), rng.random_range(0.0..=width)))
+            .collect()
+    }
+
+    /// Points around `clusters` centres with a spread of a twentieth of the width, kept
+    /// inside the square, and the cluster of each.
+    fn clustered(&self, rng: &mut StdRng, clusters: usize) -> (Vec<(f64, f64)>, Vec<usize>) {
+        let width = self.width as f64;
+        let spread = width / 20.0;
+
+        let centres: Vec<(f64, f64)> = (0..clusters)
+            .map(|_| {
+                (
+                    rng.random_range(0.1 * width..=0.9 * width),
+                    rng.random_range(0.1 * width..=0.9 * width),
+                )
+            })
+            .collect();
+
+        (0..self.cities)
+            .map(|_| {
+                let cluster = rng.random_range(0..clusters);
+                let (x, y) = centres[cluster];
+
+                // Box–Muller: two uniform draws give two independent standard normals.
+                let radius = (-2.0 * (1.0 - rng.random::<f64>()).ln()).sqrt() * spread;
+                let angle = TAU * rng.random::<f64>();
+
+                let point = (
+                    (x + radius * angle.cos()).clamp(0.0, width),
+                    (y + radius * angle.sin()).clamp(0.0, width),
+                );
+
+                (point, cluster)
+            })
+            .unzip()
+    }
+
+    fn grid(&self) -> Vec<(f64, f64)> {
+        let columns = (self.cities as f64).sqrt().ceil().max(1.0) as usize;
+        let spacing = self.width as f64 / (columns - 1).max(1) as f64;
+
+        (0..self.cities)
+            .map(|city| {
+                (
+                    (city % columns) as f64 * spacing,
+                    (city / columns) as f64 * spacing,
+                )
+            })
+            .collect()
+    }
+
+    fn circle(&self) -> Vec<(f64, f64)> {
+        let radius = self.width as f64 / 2.0;
+
+        (0..self.cities)
+            .map(|city| {
+                let angle = TAU * city as f64 / self.cities as f64;
+                (radius + radius * angle.cos(), radius + radius * angle.sin())
+            })
+            .collect()
+    }
+
+    /// Symmetric distances drawn uniformly up to the width, then replaced by the shortest
+    /// path between each pair so that no detour is ever shorter.
+    fn random_metric(&self, rng: &mut StdRng) -> Vec<Vec<u32>> {
+        let len = self.cities;
+        let mut matrix = vec![vec![0; len]; len];
+
+        (0..len).for_each(|i| {
+            (i + 1..len).for_each(|j| {
+                let distance = rng.random_range(1..=self.width.max(1));
+                matrix[i][j] = distance;
+                matrix[j][i] = distance;
+            })
+        });
+
+        (0..len).for_each(|via| {
+            (0..len).for_each(|i| {
+                (0..len).for_each(|j| {
+                    let detour = matrix[i][via] + matrix[via][j];
+
+                    if detour < matrix[i][j] {
+                        matrix[i][j] = detour;
+                    }
+                })
+            })
+        });
+
+        matrix
+    }
+}
+
+/// A generated instance, ready to be written in the project's format or TSPLIB.
+pub struct Instance {
+    pub kind: Kind,
+    pub seed: u64,
+    /// City and country of every city.
+    pub names: Vec<(String, String)>,
+    /// Where each city lies, for the Euclidean kinds.
+    pub coordinates: Option<Vec<(f64, f64)>>,
+    pub matrix: Vec<Vec<u32>>,
+}
+
+impl Instance {
+    /// Writes TSPLIB to a `.tsp` file and `City,Country,City,Country,distance` rows to
+    /// any other.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tsplib = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("tsp"));
+
+        let text = if tsplib {
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("instance");
+
+            self.tsplib(name)
+        } else {
+            self.native()
+        };
+
+        fs::write(path, text)
+    }
+
+    /// One row per pair of cities, as the files in `data/` have them.
+    pub fn native(&self) -> String {
+        let len = self.names.len();
+        let mut lines: Vec<String> = (0..len)
+            .flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let ((from, from_country), (to, to_country)) = (&self.names[i], &self.names[j]);
+                format!(
+                    "{from},{from_country},{to},{to_country},{}",
+                    self.matrix[i][j]
+                )
+            })
+            .collect();
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /// Coordinates with `EUC_2D` distances for the Euclidean kinds, and the full matrix
+    /// otherwise. Cities are numbered from 1 in the order of `names`.
+    pub fn tsplib(&self, name: &str) -> String {
+        let mut lines = vec![
+            format!("NAME: {name}"),
+            format!(
+                "COMMENT: {} instance of {} cities, seed {}",
+                self.kind,
+                self.names.len(),
+                self.seed
+            ),
+            "TYPE: TSP".to_string(),
+            format!("DIMENSION: {}", self.names.len()),
+        ];
+
+        match &self.coordinates {
+            Some(points) => {
+                lines.push("EDGE_WEIGHT_TYPE: EUC_2D".to_string());
+                lines.push("NODE_COORD_SECTION".to_string());
+                lines.extend(
+                    points
+                        .iter()
+                        .enumerate()
+                        .map(|(city, (x, y))| format!("{} {x} {y}", city + 1)),
+                );
+            }
+            None => {
+                lines.push("EDGE_WEIGHT_TYPE: EXPLICIT".to_string());
+                lines.push("EDGE_WEIGHT_FORMAT: FULL_MATRIX".to_string());
+                lines.push("EDGE_WEIGHT_SECTION".to_string());
+                lines.extend(self.matrix.iter().map(|row| {
+                    row.iter()
+                        .map(|distance| distance.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                }));
+            }
+        }
+
+        lines.push("EOF".to_string());
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Distances between the points rounded to the nearest km.
+fn euclidean(points: &[(f64, f64)]) -> Vec<Vec<u32>> {
+    points
+        .iter()
+        .map(|&(x1, y1)| {
+            points
+                .iter()
+                .map(|&(x2, y2)| ((x1 - x2).hypot(y1 - y2) + 0.5) as u32)
+                .collect()
+        })
+        .collect()
+}
+
+/// Picks `regions` distinct cities as capitals and puts every city in the region of the
+/// nearest one.
+fn nearest_capital(matrix: &[Vec<u32>], rng: &mut StdRng, regions: usize) -> Vec<usize> {
+    let capitals = index::sample(rng, matrix.len(), regions.min(matrix.len())).into_vec();
+
+    (0..matrix.len())
+        .map(|city| {
+            (0..capitals.len())
+                .min_by_key(|&region| matrix[capitals[region]][city])
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Made-up names of two or three syllables, numbered when the syllables repeat a name
+/// already given.
+#[derive(Default)]
+struct Names {
+    used: HashSet<String>,
+}
+
+impl Names {
+    fn next(&mut self, rng: &mut StdRng) -> String {
+        let syllables = rng.random_range(2..=3);
+        let name: String = (0..syllables)
+            .map(|_| SYLLABLES[rng.random_range(0..SYLLABLES.len())])
+            .collect();
+
+        let mut chars = name.chars();
+        let name: String = match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => name,
+        };
+
+        // Only one name is ever given while `used` has a particular size, so the number
+        // keeps numbered names apart, and syllables never end in a digit.
+        let name = if self.used.contains(&name) {
+            format!("{name}{}", self.used.len())
+        } else {
+            name
+        };
+
+        self.used.insert(name.clone());
+        name
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use petgraph::graph::NodeIndex;
+
+use crate::node::Node;
+use crate::solvers::route::Route;
+
+/// Something that keeps an itinerary from being a tour through every city.
+pub enum Problem {
+    Unknown(String),
+    /// A bare city name shared by cities in several countries.
+    Ambiguous(String),
+    Duplicate(String),
+    Missing(String),
+    /// Two consecutive cities with no distance between them in the dataset.
+    NoLeg(String, String),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Unknown(name) => write!(f, "UNKNOWN: {name}"),
+            Problem::Ambiguous(name) => write!(f, "AMBIGUOUS: {name}, add the country"),
+            Problem::Duplicate(name) => write!(f, "DUPLICATE: {name}"),
+            Problem::Missing(name) => write!(f, "MISSING: {name}"),
+            Problem::NoLeg(from, to) => write!(f, "NO LEG: {from} → {to}"),
+        }
+    }
+}
+
+/// A hand-made tour checked against the loaded cities. It is a closed tour when it ends
+/// where it starts and an open path otherwise.
+pub struct Itinerary {
+    /// The cities that were recognised, in the order given.
+    pub path: Vec<usize>,
+    /// Every pair of consecutive cities with its distance, if the dataset has one.
+    pub legs: Vec<(usize, usize, Option<u32>)>,
+    pub problems: Vec<Problem>,
+}
+
+impl Itinerary {
+    /// Reads one city per line, or cities separated by `;`, each as `City,Country` or a
+    /// bare city name. Blank lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> Vec<String> {
+        text.lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split(';'))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    pub fn new(names: &[String], nodes: &HashMap<NodeIndex, Node>) -> Self {
+        let key = |name: &str| {
+            name.split(',')
+                .map(|part| part.trim().to_lowercase())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let mut by_name: HashMap<String, Option<usize>> = HashMap::new();
+        nodes.values().for_each(|node| {
+            by_name.insert(key(&node.name), Some(node.id.index()));
+            by_name
+                .entry(key(node.name.split(',').next().unwrap_or_default()))
+                .and_modify(|city| *city = None)
+                .or_insert(Some(node.id.index()));
+        });
+
+        let name = |city: usize| nodes[&NodeIndex::new(city)].name.clone();
+        let mut problems = vec![];
+
+        let path: Vec<usize> = names
+            .iter()
+            .filter_map(|given| match by_name.get(&key(given)) {
+                Some(Some(city)) => Some(*city),
+                Some(None) => {
+                    problems.push(Problem::Ambiguous(given.clone()));
+                    None
+                }
+                None => {
+                    problems.push(Problem::Unknown(given.clone()));
+                    None
+                }
+            })
+            .collect();
+
+        let closed = path.len() > 1 && path.first() == path.last();
+        let visits = if closed {
+            &path[..path.len() - 1]
+        } else {
+            &path[..]
+        };
+
+        let mut seen = vec![false; nodes.len()];
+        visits.iter().for_each(|&city| {
+            if seen[city] {
+                problems.push(Problem::Duplicate(name(city)));
+            }
+
+            seen[city] = true;
+        });
+
+        let mut missing: Vec<String> = (0..nodes.len())
+            .filter(|&city| !seen[city])
+            .map(name)
+            .collect();
+        missing.sort();
+        problems.extend(missing.into_iter().map(Problem::Missing));
+
+        let legs: Vec<(usize, usize, Option<u32>)> = path
+            .windows(2)
+            .map(|pair| {
+                let distance = nodes[&NodeIndex::new(pair[0])]
+                    .neighbours
+                    .iter()
+                    .find(|neighbour| neighbour.0.index() == pair[1])
+                    .map(|neighbour| neighbour.1);
+
+                (pair[0], pair[1], distance)
+            })
+            .collect();
+
+        legs.iter()
+            .filter(|leg| leg.2.is_none())
+            .for_each(|leg| problems.push(Problem::NoLeg(name(leg.0), name(leg.1))));
+
+        Self {
+            path,
+            legs,
+            problems,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty() && !self.path.is_empty()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.path.len() > 1 && self.path.first() == self.path.last()
+    }
+
+    /// Total distance of the legs the dataset knows.
+    pub fn cost(&self) -> u32 {
+        self.legs.iter().filter_map(|leg| leg.2).sum()
+    }
+
+    /// Legs the dataset has no distance for, which leave the cost unknown.
+    pub fn unknown_legs(&self) -> usize {
+        self.legs.iter().filter(|leg| leg.2.is_none()).count()
+    }
+
+    /// Distinct cities on the path, however often they are repeated.
+    pub fn cities(&self) -> usize {
+        self.path.iter().collect::<HashSet<_>>().len()
+    }
+
+    /// The shape a solver has to match to compare against this itinerary.
+    pub fn route(&self) -> Route {
+        if self.is_closed() {
+            Route::Closed
+        } else {
+            Route::Open {
+                start: self.path.first().copied().unwrap_or(0),
+                end: self.path.last().copied(),
+            }
+        }
+    }
+}
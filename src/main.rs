@@ -1,116 +1,787 @@
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::time::Instant;
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::time::{Duration, Instant};
 
 use eframe::{App, CreationContext, NativeOptions, run_native};
+use egui::{ColorImage, Pos2, Rect, Vec2};
 use egui_graphs::{
-    Graph, LayoutRandom, LayoutStateRandom, SettingsInteraction, SettingsNavigation, SettingsStyle,
+    DefaultNodeShape, Graph, LayoutRandom, LayoutStateRandom, SettingsInteraction,
+    SettingsNavigation, SettingsStyle,
 };
+use export::{Format, Solution};
+use figure::{Drawing, Figure};
+use generator::{Generator, Kind};
+use itinerary::Itinerary;
 use node::Node;
-use petgraph::Undirected;
-use petgraph::graph::NodeIndex;
-use petgraph::stable_graph::StableGraph;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::stable_graph::{DefaultIx, StableGraph};
+use petgraph::{Directed, EdgeType, Undirected};
+use route_edge::{ROUTE_COLOURS, RouteEdge};
+use solvers::aco::{AntColonySolver, AntVariant};
+use solvers::annealing::{CoolingSchedule, SimulatedAnnealingSolver};
+use solvers::cvrp::{Construction, VehicleRoutingSolver};
 use solvers::dp::DPSolver;
+use solvers::ga::adaptive::RateSchedule;
+use solvers::ga::checkpoint::{Checkpoint, Strategy};
+use solvers::ga::config::{GAConfig, Improvement};
 use solvers::ga::ga_trait::GeneticAlgorithm;
+use solvers::ga::island::{IslandGASolver, MigrationTopology};
 use solvers::ga::parallel::ParallelGASolver;
+use solvers::ga::replacement::Replacement;
 use solvers::ga::sequential::SequentialGASolver;
+use solvers::gtsp::GeneralisedSolver;
+use solvers::lin_kernighan::{CandidateSet, LinKernighanSolver};
+use solvers::local_search::LocalSearchSolver;
+use solvers::mtsp::{MultiSolver, Objective};
+use solvers::orienteering::{OrienteeringSolver, Variant};
+use solvers::portfolio::{DP_MEMORY_BUDGET, PortfolioSolver};
+use solvers::precedence::Precedence;
+use solvers::route::Route;
+use solvers::tabu::TabuSearchSolver;
+use solvers::time_windows::{TimeWindowSolver, Window};
 
+mod export;
+mod figure;
+mod generator;
+mod itinerary;
 mod node;
+mod recording;
+mod route_edge;
 mod solvers;
 
-type GraphTuple = (
-    StableGraph<String, (), Undirected>,
+const DATA: &str = "data/100.txt";
+
+type View<Ty> = Graph<String, (), Ty, DefaultIx, DefaultNodeShape, RouteEdge>;
+
+type GraphTuple<Ty> = (
+    StableGraph<String, (), Ty>,
     Vec<Vec<u32>>,
     HashMap<NodeIndex, Node>,
 );
 
+/// Files named on the command line.
+struct Input {
+    data: String,
+    directed: bool,
+    /// Per-city `City,Country,open,close,service` rows.
+    cities: Option<String>,
+    /// Travel times in the same rows as `data`; the distances are used when unset.
+    times: Option<String>,
+    /// Vehicle capacity for routing from the command line without the window.
+    capacity: Option<u32>,
+    /// `City,Country,City,Country` rows: the first city is visited before the second.
+    precedence: Option<String>,
+    /// Itinerary to check and price without the window: a `.tour` file or `;`-separated
+    /// city names.
+    tour: Option<String>,
+    /// File the headless solution is written to, in the format its extension names.
+    export: Option<String>,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self {
+            data: DATA.to_string(),
+            directed: false,
+            cities: None,
+            times: None,
+            capacity: None,
+            precedence: None,
+            tour: None,
+            export: None,
+        }
+    }
+}
+
 enum SolutionStrategy {
     HeldKarp,
     GeneticAlgorithm,
     GeneticAlgorithmParallel,
+    GeneticAlgorithmIslands,
+    LocalSearch,
+    LinKernighan,
+    SimulatedAnnealing,
+    AntColony,
+    TabuSearch,
+    Portfolio,
+    Generalised,
+    TimeWindows,
+    Orienteering,
+}
+
+/// The view stepping through the best tours of a genetic algorithm run.
+struct Playback {
+    frames: Vec<Improvement>,
+    next: usize,
+    shown: Instant,
+    /// Screenshots of the frames shown so far, when the replay is being saved.
+    recorded: Option<Vec<ColorImage>>,
+    /// Whether the screenshot of the frame last shown is still to come.
+    waiting: bool,
 }
 
-pub struct Pathfinder {
-    g: Graph<String, (), Undirected>,
+pub struct Pathfinder<Ty: EdgeType> {
+    g: View<Ty>,
+    /// The whole instance as loaded, which the view is rebuilt from to draw routes.
+    graph: StableGraph<String, (), Ty>,
     final_cost: String,
     nodes: HashMap<NodeIndex, Node>,
+    matrix: Vec<Vec<u32>>,
     dp_solver: DPSolver,
     ga_solver: SequentialGASolver,
     parallel_solver: ParallelGASolver,
+    island_solver: IslandGASolver,
+    local_search: LocalSearchSolver,
+    lk_solver: LinKernighanSolver,
+    sa_solver: SimulatedAnnealingSolver,
+    aco_solver: AntColonySolver,
+    tabu_solver: TabuSearchSolver,
+    portfolio_solver: PortfolioSolver,
+    gtsp_solver: GeneralisedSolver,
+    tw_solver: TimeWindowSolver,
+    mtsp_solver: MultiSolver,
+    vrp_solver: VehicleRoutingSolver,
+    prize_solver: OrienteeringSolver,
+    polish: bool,
+    route: Route,
+    precedence: Precedence,
+    /// `.tour` file evaluated by the Evaluate button.
+    tour_path: String,
+    /// Cost and routes last shown, kept for Save solution.
+    solution: Option<(u32, Vec<Vec<usize>>)>,
+    export_path: String,
+    export_format: Format,
+    figure: Figure,
+    /// Every new best tour of the last genetic algorithm run, oldest first.
+    replay: Vec<Improvement>,
+    playback: Option<Playback>,
+    /// Whether the next screenshot is saved as a picture of the graph view.
+    screenshot: bool,
+    /// Where the graph view was drawn in the last frame.
+    canvas: Rect,
+    /// Schedule of the last time-window tour, one line per stop.
+    report: Vec<String>,
 }
 
-impl Pathfinder {
-    fn new(_: &CreationContext<'_>) -> Self {
-        let (graph, matrix, nodes) = load_graph();
+impl<Ty: EdgeType> Pathfinder<Ty> {
+    fn new(_: &CreationContext<'_>, input: &Input) -> Self {
+        let (graph, matrix, mut nodes) = load_graph(&input.data);
+
+        let depot = match &input.cities {
+            Some(cities) => load_cities(cities, &mut nodes),
+            None => None,
+        };
 
-        let mut g = Graph::from(&graph);
+        let times = match &input.times {
+            Some(times) => load_times::<Ty>(times, &nodes, &matrix),
+            None => matrix.clone(),
+        };
 
-        nodes.values().for_each(|node| {
-            g.node_mut(node.id).unwrap().set_label(node.name.clone());
+        let precedence = match &input.precedence {
+            Some(precedence) => load_precedence(precedence, &nodes),
+            None => Precedence::default(),
+        };
 
-            node.neighbours.iter().for_each(|edge| {
-                g.edge_mut(edge.2.unwrap())
-                    .unwrap()
-                    .set_label(edge.1.to_string())
-            });
+        let windows: Vec<Window> = (0..nodes.len())
+            .map(|city| nodes[&NodeIndex::new(city)].window)
+            .collect();
+
+        let (demands, names): (Vec<u32>, Vec<String>) = (0..nodes.len())
+            .map(|city| {
+                let node = &nodes[&NodeIndex::new(city)];
+                (node.demand, node.name.clone())
+            })
+            .unzip();
+
+        let mut tw_solver = TimeWindowSolver::new(matrix.clone(), times, windows);
+        tw_solver.depot = depot.map_or(0, |depot| depot.index());
+
+        let mut vrp_solver = VehicleRoutingSolver::new(matrix.clone(), demands, names);
+        vrp_solver.depot = tw_solver.depot;
+
+        let (prizes, penalties): (Vec<u32>, Vec<u32>) = (0..nodes.len())
+            .map(|city| {
+                let node = &nodes[&NodeIndex::new(city)];
+                (node.prize, node.penalty)
+            })
+            .unzip();
+
+        let mut prize_solver = OrienteeringSolver::new(matrix.clone(), prizes, penalties);
+        prize_solver.depot = tw_solver.depot;
+
+        let mut ga_solver = SequentialGASolver::new(matrix.clone());
+        let mut parallel_solver = ParallelGASolver::new(matrix.clone());
+        let mut island_solver = IslandGASolver::new(matrix.clone());
+
+        [
+            &mut ga_solver.config,
+            &mut parallel_solver.config,
+            &mut island_solver.ga.config,
+        ]
+        .into_iter()
+        .for_each(|config| {
+            config.data = input.data.clone();
+            config.directed = input.directed;
         });
 
+        let groups: Vec<String> = (0..nodes.len())
+            .map(|city| nodes[&NodeIndex::new(city)].group().to_string())
+            .collect();
+
         Self {
-            g,
+            g: view(&graph, &nodes),
+            graph,
             final_cost: "".to_string(),
             nodes,
+            matrix: matrix.clone(),
             dp_solver: DPSolver::new(matrix.clone()),
-            ga_solver: SequentialGASolver::new(matrix.clone()),
-            parallel_solver: ParallelGASolver::new(matrix),
+            ga_solver,
+            parallel_solver,
+            island_solver,
+            local_search: LocalSearchSolver::new(matrix.clone()),
+            lk_solver: LinKernighanSolver::new(matrix.clone()),
+            sa_solver: SimulatedAnnealingSolver::new(matrix.clone()),
+            aco_solver: AntColonySolver::new(matrix.clone()),
+            tabu_solver: TabuSearchSolver::new(matrix.clone()),
+            portfolio_solver: PortfolioSolver::new(matrix.clone()),
+            mtsp_solver: MultiSolver::new(matrix.clone()),
+            gtsp_solver: GeneralisedSolver::new(matrix, &groups),
+            tw_solver,
+            vrp_solver,
+            prize_solver,
+            polish: false,
+            route: Route::Closed,
+            precedence,
+            tour_path: String::new(),
+            solution: None,
+            export_path: "solution".to_string(),
+            export_format: Format::Json,
+            figure: Figure::Svg,
+            replay: vec![],
+            playback: None,
+            screenshot: false,
+            canvas: Rect::ZERO,
+            report: vec![],
         }
     }
 
     fn solve(&mut self, strategy: SolutionStrategy) {
+        let supports_open = matches!(
+            strategy,
+            SolutionStrategy::HeldKarp
+                | SolutionStrategy::GeneticAlgorithm
+                | SolutionStrategy::GeneticAlgorithmParallel
+                | SolutionStrategy::GeneticAlgorithmIslands
+        );
+
+        if self.route != Route::Closed && !supports_open {
+            println!("OPEN PATH: only Held-Karp and the genetic algorithms solve open paths");
+            return;
+        }
+
+        self.dp_solver.route = self.route;
+        self.ga_solver.config.route = self.route;
+        self.parallel_solver.config.route = self.route;
+        self.island_solver.ga.config.route = self.route;
+        self.dp_solver.precedence = self.precedence.clone();
+        self.ga_solver.config.precedence = self.precedence.clone();
+        self.parallel_solver.config.precedence = self.precedence.clone();
+        self.island_solver.ga.config.precedence = self.precedence.clone();
+        self.report.clear();
+
         let now = Instant::now();
+        let (sender, receiver) = mpsc::channel();
+        self.report_progress(Some(sender));
 
         let (cost, path) = match strategy {
             SolutionStrategy::HeldKarp => self.dp_solver.solve(),
             SolutionStrategy::GeneticAlgorithm => self.ga_solver.solve(),
             SolutionStrategy::GeneticAlgorithmParallel => self.parallel_solver.solve(),
+            SolutionStrategy::GeneticAlgorithmIslands => self.island_solver.solve(),
+            SolutionStrategy::LocalSearch => self.local_search.solve(),
+            SolutionStrategy::LinKernighan => self.lk_solver.solve(),
+            SolutionStrategy::SimulatedAnnealing => self.sa_solver.solve(),
+            SolutionStrategy::AntColony => self.aco_solver.solve(),
+            SolutionStrategy::TabuSearch => self.tabu_solver.solve(),
+            SolutionStrategy::Portfolio => self.portfolio_solver.solve(),
+            SolutionStrategy::Generalised => self.gtsp_solver.solve(),
+            SolutionStrategy::TimeWindows => self.tw_solver.solve(),
+            SolutionStrategy::Orienteering => self.prize_solver.solve(),
+        };
+
+        self.report_progress(None);
+        self.replay = receiver.try_iter().collect();
+
+        // Polishing ignores the windows, so a time-window tour is shown as found. Prize
+        // tours are polished while they are built.
+        match strategy {
+            SolutionStrategy::TimeWindows => {
+                self.report = self.schedule(&path);
+                self.show(now, cost, path, false);
+            }
+            SolutionStrategy::Orienteering => {
+                self.report = self.prize_report(&path);
+                self.show(now, cost, path, false);
+            }
+            _ => self.show(now, cost, path, self.polish),
+        }
+    }
+
+    /// The prize collected or penalties paid by a prize tour, and the cities it skips.
+    fn prize_report(&self, path: &[usize]) -> Vec<String> {
+        let solver = &self.prize_solver;
+        let length = solver.length(path);
+
+        let summary = match solver.variant {
+            Variant::Orienteering => format!(
+                "PRIZE: {} of {}, length {length} of budget {}",
+                solver.prize(path),
+                solver.prize(&solver.customers()),
+                solver.budget
+            ),
+            Variant::PrizeCollecting => format!(
+                "LENGTH: {length}, penalties {}, prize {}",
+                solver.penalty(path),
+                solver.prize(path)
+            ),
+        };
+
+        let skipped: Vec<&str> = solver
+            .customers()
+            .into_iter()
+            .filter(|city| !path.contains(city))
+            .map(|city| self.nodes[&NodeIndex::new(city)].name.as_str())
+            .collect();
+
+        if skipped.is_empty() {
+            vec![summary]
+        } else {
+            vec![summary, format!("SKIPPED: {}", skipped.join("; "))]
+        }
+    }
+
+    /// One line per stop of a time-window tour: when it is reached, its window, and how
+    /// long the vehicle waits for it to open or how late it is.
+    fn schedule(&self, path: &[usize]) -> Vec<String> {
+        self.tw_solver
+            .schedule(path)
+            .iter()
+            .map(|stop| {
+                let node = &self.nodes[&NodeIndex::new(stop.city)];
+                let window = match node.window.close {
+                    u32::MAX => format!("{}–", node.window.open),
+                    close => format!("{}–{close}", node.window.open),
+                };
+
+                let line = format!("ARRIVE {}: {} ({window})", node.name, stop.arrival);
+
+                if stop.late > 0 {
+                    format!("{line}, LATE by {}", stop.late)
+                } else if stop.start > stop.arrival {
+                    format!("{line}, waits {}", stop.start - stop.arrival)
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    /// Where the genetic algorithms send each new best tour, or nowhere.
+    fn report_progress(&mut self, progress: Option<Sender<Improvement>>) {
+        self.ga_solver.config.progress = progress.clone();
+        self.parallel_solver.config.progress = progress.clone();
+        self.island_solver.ga.config.progress = progress;
+    }
+
+    fn resume(&mut self, strategy: SolutionStrategy) {
+        let now = Instant::now();
+        let (sender, receiver) = mpsc::channel();
+        self.report_progress(Some(sender));
+
+        let resumed = match strategy {
+            SolutionStrategy::GeneticAlgorithm => Checkpoint::load(
+                Path::new(&self.ga_solver.config.checkpoint_path),
+                &self.matrix,
+            )
+            .and_then(|checkpoint| self.ga_solver.resume(checkpoint)),
+            SolutionStrategy::GeneticAlgorithmParallel => Checkpoint::load(
+                Path::new(&self.parallel_solver.config.checkpoint_path),
+                &self.matrix,
+            )
+            .and_then(|checkpoint| self.parallel_solver.resume(checkpoint)),
+            SolutionStrategy::GeneticAlgorithmIslands => Checkpoint::load(
+                Path::new(&self.island_solver.ga.config.checkpoint_path),
+                &self.matrix,
+            )
+            .and_then(|checkpoint| self.island_solver.resume(checkpoint)),
+            _ => return,
+        };
+
+        self.report_progress(None);
+        self.replay = receiver.try_iter().collect();
+
+        match resumed {
+            Ok((cost, path)) => self.show(now, cost, path, self.polish),
+            Err(err) => println!("RESUME FAILED: {err}"),
+        }
+    }
+
+    fn show(&mut self, now: Instant, cost: u32, path: Vec<usize>, polish: bool) {
+        // Local search only polishes closed tours, and ignores precedence pairs, so a
+        // polished tour that breaks more of them is dropped.
+        let broken = |path: &[usize]| self.precedence.violations(path).len();
+
+        let (cost, path) = if polish && path.first() == path.last() {
+            let polished = self.local_search.improve(&path);
+
+            if broken(&polished.1) > broken(&path) {
+                (cost, path)
+            } else {
+                polished
+            }
+        } else {
+            (cost, path)
         };
 
+        if !self.precedence.is_empty() && !path.is_empty() {
+            let report = self.precedence_report(&path);
+            self.report.extend(report);
+        }
+
+        self.show_routes(now, cost, vec![path]);
+    }
+
+    /// One line per precedence pair the path breaks, or a single line when it keeps them
+    /// all. Only Held-Karp and the genetic algorithms enforce the pairs.
+    fn precedence_report(&self, path: &[usize]) -> Vec<String> {
+        let name = |city: usize| &self.nodes[&NodeIndex::new(city)].name;
+        let violations = self.precedence.violations(path);
+
+        if violations.is_empty() {
+            return vec![format!(
+                "PRECEDENCE: all {} pairs kept",
+                self.precedence.pairs().len()
+            )];
+        }
+
+        violations
+            .iter()
+            .map(|&(before, after)| format!("VIOLATED: {} before {}", name(before), name(after)))
+            .collect()
+    }
+
+    fn show_routes(&mut self, now: Instant, cost: u32, routes: Vec<Vec<usize>>) {
         println!("ELAPSED: {}ms", now.elapsed().as_millis());
         println!("COST: {cost}");
+        self.report.iter().for_each(|line| println!("{line}"));
 
         self.final_cost = cost.to_string();
+        self.draw_routes(&routes);
+        self.solution = Some((cost, routes));
+    }
+
+    /// Keeps only the edges of the routes in the graph, each route in its own colour when
+    /// there is more than one. The nodes stay where they are.
+    fn draw_routes(&mut self, routes: &[Vec<usize>]) {
+        let mut g = view(&self.graph, &self.nodes);
+
+        self.g.nodes_iter().for_each(|(idx, node)| {
+            if let Some(moved) = g.node_mut(idx) {
+                moved.set_location(node.location());
+            }
+        });
 
-        let edges: Vec<usize> = path
-            .windows(2)
-            .filter_map(|pair| {
-                if let Some(val) = self.nodes.get(&NodeIndex::new(pair[0])) {
-                    val.neighbours.iter().find_map(|n| {
-                        if n.0.index() == pair[1] {
-                            Some(n.2.unwrap().index())
+        self.g = g;
+
+        let route_edges: Vec<Vec<usize>> = routes
+            .iter()
+            .map(|path| {
+                path.windows(2)
+                    .filter_map(|pair| {
+                        if let Some(val) = self.nodes.get(&NodeIndex::new(pair[0])) {
+                            val.neighbours.iter().find_map(|n| {
+                                if n.0.index() == pair[1] {
+                                    Some(n.2.unwrap().index())
+                                } else {
+                                    None
+                                }
+                            })
                         } else {
                             None
                         }
                     })
-                } else {
-                    None
-                }
+                    .collect()
             })
             .collect();
 
+        let edges: Vec<usize> = route_edges.concat();
+
         self.nodes
             .values()
             .flat_map(|node| node.get_edge_idxs(&edges))
             .for_each(|edge| {
                 let _ = self.g.remove_edge(edge);
             });
+
+        if routes.len() > 1 {
+            route_edges.iter().enumerate().for_each(|(route, edges)| {
+                let (colour, _) = ROUTE_COLOURS[route % ROUTE_COLOURS.len()];
+
+                edges.iter().for_each(|&edge| {
+                    if let Some(edge) = self.g.edge_mut(EdgeIndex::new(edge)) {
+                        edge.display_mut().colour = Some(colour);
+                    }
+                });
+            });
+        }
+    }
+
+    /// Checks the itinerary in the tour file and draws the legs the dataset knows.
+    fn evaluate(&mut self) {
+        let now = Instant::now();
+
+        let names = match fs::read_to_string(&self.tour_path) {
+            Ok(text) => Itinerary::parse(&text),
+            Err(err) => return println!("EVALUATE: {err}"),
+        };
+
+        let itinerary = Itinerary::new(&names, &self.nodes);
+        self.report = itinerary_report(&itinerary, &self.nodes, &self.matrix, &self.precedence);
+
+        if itinerary.unknown_legs() > 0 {
+            self.report.insert(0, itinerary_cost(&itinerary));
+        }
+        self.show_routes(now, itinerary.cost(), vec![itinerary.path]);
+    }
+
+    /// Writes the last solution next to the export path, with the chosen format's
+    /// extension.
+    fn save_solution(&self) {
+        let Some((cost, routes)) = &self.solution else {
+            return println!("SAVE: nothing solved yet");
+        };
+
+        let path = Path::new(&self.export_path).with_extension(self.export_format.extension());
+        let solution = Solution {
+            cost: *cost,
+            routes,
+            nodes: &self.nodes,
+            matrix: &self.matrix,
+        };
+
+        match solution.save(&path, self.export_format) {
+            Ok(()) => println!("SAVED: {}", path.display()),
+            Err(err) => println!("SAVE FAILED: {err}"),
+        }
+    }
+
+    /// Writes the whole instance with the last routes as Graphviz source, or the graph
+    /// view as it is laid out now as SVG.
+    fn save_figure(&self) {
+        let path = Path::new(&self.export_path).with_extension(self.figure.extension());
+        let routes = self
+            .solution
+            .as_ref()
+            .map_or(&[][..], |(_, routes)| routes.as_slice());
+
+        let figure = match self.figure {
+            Figure::Dot => figure::dot(&self.graph, &self.matrix, routes),
+            Figure::Svg => self.drawing().svg(),
+        };
+
+        match fs::write(&path, figure) {
+            Ok(()) => println!("SAVED: {}", path.display()),
+            Err(err) => println!("SAVE FAILED: {err}"),
+        }
+    }
+
+    /// The graph view's nodes where they are now and the edges it still shows.
+    fn drawing(&self) -> Drawing {
+        let edges = self
+            .g
+            .edges_iter()
+            .filter_map(|(idx, edge)| {
+                let (from, to) = self.g.edge_endpoints(idx)?;
+                Some((
+                    from.index(),
+                    to.index(),
+                    edge.label(),
+                    edge.display().colour,
+                ))
+            })
+            .collect();
+
+        Drawing {
+            names: (0..self.nodes.len())
+                .map(|city| self.nodes[&NodeIndex::new(city)].name.clone())
+                .collect(),
+            positions: (0..self.nodes.len())
+                .map(|city| self.g.node(NodeIndex::new(city)).unwrap().location())
+                .collect(),
+            edges,
+            directed: Ty::is_directed(),
+        }
+    }
+
+    /// Steps the view through the recorded best tours, taking a screenshot of each when
+    /// the replay is saved.
+    fn start_replay(&mut self, save: bool) {
+        self.playback = Some(Playback {
+            frames: recording::sample(&self.replay),
+            next: 0,
+            shown: Instant::now(),
+            recorded: save.then(Vec::new),
+            waiting: false,
+        });
+    }
+
+    /// Shows the next frame of the replay once the last one has been on screen long
+    /// enough, or captured, and puts the solution back after the last frame.
+    fn play(&mut self, ctx: &egui::Context) {
+        let Some(playback) = &mut self.playback else {
+            return;
+        };
+
+        ctx.request_repaint();
+
+        let delay = Duration::from_millis(recording::FRAME_DELAY.into());
+        let saving = playback.recorded.is_some();
+
+        if playback.waiting || (!saving && playback.next > 0 && playback.shown.elapsed() < delay) {
+            return;
+        }
+
+        let Some(frame) = playback.frames.get(playback.next).cloned() else {
+            return self.stop_replay();
+        };
+
+        playback.next += 1;
+        playback.shown = Instant::now();
+        playback.waiting = saving;
+
+        self.final_cost = format!("{} (generation {})", frame.cost, frame.generation);
+        self.draw_routes(&[frame.tour]);
+
+        if saving {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(Default::default()));
+        }
+    }
+
+    fn stop_replay(&mut self) {
+        let Some(playback) = self.playback.take() else {
+            return;
+        };
+
+        if let Some(frames) = playback.recorded {
+            let path = Path::new(&self.export_path).with_extension("png");
+
+            match recording::save_apng(&path, &frames) {
+                Ok(()) => println!("SAVED: {} ({} frames)", path.display(), frames.len()),
+                Err(err) => println!("SAVE FAILED: {err}"),
+            }
+        }
+
+        if let Some((cost, routes)) = self.solution.clone() {
+            self.final_cost = cost.to_string();
+            self.draw_routes(&routes);
+        }
+    }
+
+    /// Crops a screenshot of the window to the graph view and keeps it as the replay's
+    /// next frame, or saves it as a PNG next to the export path.
+    fn captured(&mut self, image: &ColorImage, pixels_per_point: f32) {
+        let window = Rect::from_min_size(
+            Pos2::ZERO,
+            Vec2::new(image.size[0] as f32, image.size[1] as f32) / pixels_per_point,
+        );
+        let canvas = image.region(&self.canvas.intersect(window), Some(pixels_per_point));
+
+        if let Some(playback) = &mut self.playback
+            && let Some(recorded) = &mut playback.recorded
+            && playback.waiting
+        {
+            recorded.push(canvas);
+            playback.waiting = false;
+        } else if self.screenshot {
+            self.screenshot = false;
+            let path = Path::new(&self.export_path).with_extension("png");
+
+            match recording::save_png(&path, &canvas) {
+                Ok(()) => println!("SAVED: {}", path.display()),
+                Err(err) => println!("SAVE FAILED: {err}"),
+            }
+        }
+    }
+
+    fn solve_salesmen(&mut self) {
+        if self.route != Route::Closed {
+            println!("OPEN PATH: every salesman returns to their depot");
+            return;
+        }
+
+        self.report.clear();
+
+        let now = Instant::now();
+        let (cost, routes) = self.mtsp_solver.solve();
+
+        self.report = routes
+            .iter()
+            .enumerate()
+            .map(|(salesman, route)| {
+                let (_, colour) = ROUTE_COLOURS[salesman % ROUTE_COLOURS.len()];
+
+                format!(
+                    "SALESMAN {} ({colour}) from {}: {} cities, {}",
+                    salesman + 1,
+                    self.nodes[&NodeIndex::new(route[0])].name,
+                    route.len() - 2,
+                    self.mtsp_solver.length(route),
+                )
+            })
+            .collect();
+
+        self.show_routes(now, cost, routes);
+    }
+
+    fn solve_vehicles(&mut self) {
+        if self.route != Route::Closed {
+            println!("OPEN PATH: every vehicle returns to the depot");
+            return;
+        }
+
+        self.report.clear();
+
+        let now = Instant::now();
+        let (cost, routes) = self.vrp_solver.solve();
+
+        self.report = vehicle_report(&self.vrp_solver, &routes, &self.nodes);
+        self.show_routes(now, cost, routes);
     }
 }
 
-impl App for Pathfinder {
+impl<Ty: EdgeType> App for Pathfinder<Ty> {
     fn update(&mut self, ctx: &egui::Context, _: &mut eframe::Frame) {
+        let screenshot = ctx.input(|input| {
+            input.raw.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+
+        if let Some(image) = screenshot {
+            self.captured(&image, ctx.pixels_per_point());
+        }
+
+        self.play(ctx);
+
         egui::SidePanel::right("right_panel")
             .min_width(250.)
             .show(ctx, |ui| {
                 ui.vertical(|ui| {
+                    route_settings(ui, &mut self.route, &self.nodes);
+
                     ui.label("Solve using:");
 
                     ui.vertical(|ui| {
@@ -123,105 +794,627 @@ impl App for Pathfinder {
                         if ui.button("Genetic Algorithm").clicked() {
                             self.solve(SolutionStrategy::GeneticAlgorithm);
                         };
+
+                        if ui.button("Resume").clicked() {
+                            self.resume(SolutionStrategy::GeneticAlgorithm);
+                        };
+
+                        ga_settings(ui, &mut self.ga_solver.config, "ga");
                     });
 
                     ui.vertical(|ui| {
                         if ui.button("Genetic Algorithm Parallel").clicked() {
                             self.solve(SolutionStrategy::GeneticAlgorithmParallel);
                         };
-                    });
 
-                    if !self.final_cost.is_empty() {
-                        ui.label(format!("COST: {}", self.final_cost));
-                    }
-                });
-            });
+                        if ui.button("Resume").clicked() {
+                            self.resume(SolutionStrategy::GeneticAlgorithmParallel);
+                        };
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let interaction_settings = &SettingsInteraction::new()
-                .with_dragging_enabled(true)
-                .with_node_clicking_enabled(true)
-                .with_node_selection_enabled(true)
-                .with_node_selection_multi_enabled(true)
-                .with_edge_clicking_enabled(true)
-                .with_edge_selection_enabled(true)
-                .with_edge_selection_multi_enabled(true);
-            let style_settings = &SettingsStyle::new().with_labels_always(true);
-            let navigation_settings = &SettingsNavigation::new()
-                .with_fit_to_screen_enabled(false)
-                .with_zoom_and_pan_enabled(true);
+                        ga_settings(ui, &mut self.parallel_solver.config, "parallel");
+                    });
 
-            ui.add(
-                &mut egui_graphs::GraphView::<
-                    _,
-                    _,
-                    _,
-                    _,
-                    _,
-                    _,
-                    LayoutStateRandom,
-                    LayoutRandom,
-                >::new(&mut self.g)
-                .with_styles(style_settings)
-                .with_interactions(interaction_settings)
-                .with_navigations(navigation_settings),
-            );
-        });
-    }
-}
+                    ui.vertical(|ui| {
+                        if ui.button("Genetic Algorithm Islands").clicked() {
+                            self.solve(SolutionStrategy::GeneticAlgorithmIslands);
+                        };
 
-fn load_graph() -> GraphTuple {
-    let lines = fs::read_to_string("data/100.txt")
-        .expect("Oops, could not open file.")
-        .lines()
-        .map(|line| {
-            let parsed = line.split(",").collect::<Vec<&str>>();
+                        if ui.button("Resume").clicked() {
+                            self.resume(SolutionStrategy::GeneticAlgorithmIslands);
+                        };
 
-            (
-                parsed[0].to_owned() + ", " + parsed[1],
-                parsed[2].to_owned() + ", " + parsed[3],
-                parsed[4].parse::<u32>().unwrap(),
-            )
-        })
-        .collect::<Vec<(String, String, u32)>>();
+                        egui::ComboBox::from_label("Topology")
+                            .selected_text(self.island_solver.topology.to_string())
+                            .show_ui(ui, |ui| {
+                                MigrationTopology::ALL.into_iter().for_each(|topology| {
+                                    ui.selectable_value(
+                                        &mut self.island_solver.topology,
+                                        topology,
+                                        topology.to_string(),
+                                    );
+                                });
+                            });
 
-    let mut graph: StableGraph<String, (), Undirected> = StableGraph::default();
+                        ui.add(
+                            egui::Slider::new(&mut self.island_solver.islands, 1..=32)
+                                .text("islands"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.island_solver.migration_interval, 1..=1000)
+                                .text("migration interval"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.island_solver.migration_size, 0..=20)
+                                .text("migrants"),
+                        );
+                        ga_settings(ui, &mut self.island_solver.ga.config, "islands");
+                    });
 
-    let ids = lines
-        .iter()
-        .flat_map(|line| [line.0.clone(), line.1.clone()])
-        .collect::<HashSet<String>>()
-        .into_iter()
-        .collect::<Vec<String>>();
+                    ui.vertical(|ui| {
+                        if ui.button("Local Search").clicked() {
+                            self.solve(SolutionStrategy::LocalSearch);
+                        };
+                    });
 
-    let mut node_map: HashMap<String, NodeIndex> = HashMap::new();
+                    ui.vertical(|ui| {
+                        if ui.button("Lin-Kernighan").clicked() {
+                            self.solve(SolutionStrategy::LinKernighan);
+                        };
 
-    ids.iter().for_each(|city| {
-        let idx = graph.add_node(city.clone());
-        node_map.insert(city.clone(), idx);
-    });
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut self.lk_solver.candidate_set,
+                                CandidateSet::AlphaNearness,
+                                "Alpha-nearness",
+                            );
+                            ui.radio_value(
+                                &mut self.lk_solver.candidate_set,
+                                CandidateSet::NearestNeighbours,
+                                "Nearest",
+                            );
+                        });
+                    });
 
-    let mut nodes = lines.iter().fold(
-        HashMap::new(),
-        |mut acc, curr| -> HashMap<NodeIndex, Node> {
-            let curr_id = *node_map.get(&curr.0).unwrap();
-            let end_id = *node_map.get(&curr.1).unwrap();
+                    ui.vertical(|ui| {
+                        if ui.button("Simulated Annealing").clicked() {
+                            self.solve(SolutionStrategy::SimulatedAnnealing);
+                        };
 
-            acc.entry(end_id)
-                .and_modify(|node| node.neighbours.push((curr_id, curr.2, None)))
-                .or_insert(Node {
-                    id: end_id,
-                    name: curr.1.clone(),
-                    neighbours: vec![(curr_id, curr.2, None)],
-                });
+                        egui::ComboBox::from_label("Cooling")
+                            .selected_text(self.sa_solver.cooling.to_string())
+                            .show_ui(ui, |ui| {
+                                CoolingSchedule::ALL.into_iter().for_each(|schedule| {
+                                    ui.selectable_value(
+                                        &mut self.sa_solver.cooling,
+                                        schedule,
+                                        schedule.to_string(),
+                                    );
+                                });
+                            });
+                    });
 
-            acc.entry(curr_id)
-                .and_modify(|node| node.neighbours.push((end_id, curr.2, None)))
-                .or_insert(Node {
-                    id: curr_id,
-                    name: curr.0.clone(),
-                    neighbours: vec![(end_id, curr.2, None)],
-                });
+                    ui.vertical(|ui| {
+                        if ui.button("Ant Colony").clicked() {
+                            self.solve(SolutionStrategy::AntColony);
+                        };
+
+                        egui::ComboBox::from_label("Variant")
+                            .selected_text(self.aco_solver.variant.to_string())
+                            .show_ui(ui, |ui| {
+                                AntVariant::ALL.into_iter().for_each(|variant| {
+                                    ui.selectable_value(
+                                        &mut self.aco_solver.variant,
+                                        variant,
+                                        variant.to_string(),
+                                    );
+                                });
+                            });
+
+                        ui.add(
+                            egui::Slider::new(&mut self.aco_solver.alpha, 0.0..=5.0).text("alpha"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.aco_solver.beta, 0.0..=10.0).text("beta"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.aco_solver.evaporation, 0.01..=1.0)
+                                .text("evaporation"),
+                        );
+                        ui.checkbox(&mut self.aco_solver.two_opt, "2-opt each ant");
+                    });
+
+                    ui.vertical(|ui| {
+                        if ui.button("Tabu Search").clicked() {
+                            self.solve(SolutionStrategy::TabuSearch);
+                        };
+                    });
+
+                    ui.vertical(|ui| {
+                        if ui.button("Portfolio").clicked() {
+                            self.solve(SolutionStrategy::Portfolio);
+                        };
+
+                        ui.add(
+                            egui::Slider::new(&mut self.portfolio_solver.deadline_secs, 1..=300)
+                                .text("deadline (s)"),
+                        );
+                    });
+
+                    ui.vertical(|ui| {
+                        if ui.button("One city per group (GTSP)").clicked() {
+                            self.solve(SolutionStrategy::Generalised);
+                        };
+
+                        ui.add(
+                            egui::Slider::new(&mut self.gtsp_solver.population, 10..=1000)
+                                .text("population"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.gtsp_solver.generations, 10..=5000)
+                                .text("generations"),
+                        );
+                    });
+
+                    ui.vertical(|ui| {
+                        if ui.button("Time windows").clicked() {
+                            self.solve(SolutionStrategy::TimeWindows);
+                        };
+
+                        ui.add(
+                            egui::Slider::new(&mut self.tw_solver.population, 10..=1000)
+                                .text("population"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.tw_solver.generations, 10..=10000)
+                                .text("generations"),
+                        );
+                    });
+
+                    ui.vertical(|ui| {
+                        if ui.button("Multiple salesmen (mTSP)").clicked() {
+                            self.solve_salesmen();
+                        };
+
+                        egui::ComboBox::from_label("Objective")
+                            .selected_text(self.mtsp_solver.objective.to_string())
+                            .show_ui(ui, |ui| {
+                                Objective::ALL.into_iter().for_each(|objective| {
+                                    ui.selectable_value(
+                                        &mut self.mtsp_solver.objective,
+                                        objective,
+                                        objective.to_string(),
+                                    );
+                                });
+                            });
+
+                        ui.add(
+                            egui::Slider::new(&mut self.mtsp_solver.salesmen, 1..=8)
+                                .text("salesmen"),
+                        );
+                        depot_settings(ui, &mut self.mtsp_solver, &self.nodes);
+                        ui.add(
+                            egui::Slider::new(&mut self.mtsp_solver.population, 10..=1000)
+                                .text("population"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.mtsp_solver.generations, 10..=10000)
+                                .text("generations"),
+                        );
+                    });
+
+                    ui.vertical(|ui| {
+                        if ui.button("Vehicle routing (CVRP)").clicked() {
+                            self.solve_vehicles();
+                        };
+
+                        egui::ComboBox::from_label("Construction")
+                            .selected_text(self.vrp_solver.construction.to_string())
+                            .show_ui(ui, |ui| {
+                                Construction::ALL.into_iter().for_each(|construction| {
+                                    ui.selectable_value(
+                                        &mut self.vrp_solver.construction,
+                                        construction,
+                                        construction.to_string(),
+                                    );
+                                });
+                            });
+
+                        ui.add(
+                            egui::Slider::new(&mut self.vrp_solver.capacity, 1..=1000)
+                                .logarithmic(true)
+                                .text("capacity"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.vrp_solver.population, 10..=1000)
+                                .text("population"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.vrp_solver.generations, 10..=5000)
+                                .text("generations"),
+                        );
+                    });
+
+                    ui.vertical(|ui| {
+                        if ui.button("Prize tour").clicked() {
+                            self.solve(SolutionStrategy::Orienteering);
+                        };
+
+                        egui::ComboBox::from_label("Prize variant")
+                            .selected_text(self.prize_solver.variant.to_string())
+                            .show_ui(ui, |ui| {
+                                Variant::ALL.into_iter().for_each(|variant| {
+                                    ui.selectable_value(
+                                        &mut self.prize_solver.variant,
+                                        variant,
+                                        variant.to_string(),
+                                    );
+                                });
+                            });
+
+                        ui.add(
+                            egui::Slider::new(&mut self.prize_solver.budget, 0..=100000)
+                                .logarithmic(true)
+                                .text("budget (km)"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.prize_solver.population, 10..=1000)
+                                .text("population"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut self.prize_solver.generations, 10..=5000)
+                                .text("generations"),
+                        );
+                    });
+
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Tour");
+                            ui.text_edit_singleline(&mut self.tour_path);
+                        });
+
+                        if ui.button("Evaluate tour").clicked() {
+                            self.evaluate();
+                        };
+                    });
+
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("File");
+                            ui.text_edit_singleline(&mut self.export_path);
+                        });
+
+                        egui::ComboBox::from_label("Format")
+                            .selected_text(self.export_format.to_string())
+                            .show_ui(ui, |ui| {
+                                Format::ALL.into_iter().for_each(|format| {
+                                    ui.selectable_value(
+                                        &mut self.export_format,
+                                        format,
+                                        format.to_string(),
+                                    );
+                                });
+                            });
+
+                        if ui.button("Save solution").clicked() {
+                            self.save_solution();
+                        };
+
+                        egui::ComboBox::from_label("Figure")
+                            .selected_text(self.figure.to_string())
+                            .show_ui(ui, |ui| {
+                                Figure::ALL.into_iter().for_each(|figure| {
+                                    ui.selectable_value(
+                                        &mut self.figure,
+                                        figure,
+                                        figure.to_string(),
+                                    );
+                                });
+                            });
+
+                        if ui.button("Save figure").clicked() {
+                            self.save_figure();
+                        };
+
+                        if ui.button("Save screenshot").clicked() {
+                            self.screenshot = true;
+                            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(
+                                Default::default(),
+                            ));
+                        };
+
+                        let replayable = !self.replay.is_empty() && self.playback.is_none();
+
+                        ui.add_enabled_ui(replayable, |ui| {
+                            ui.horizontal(|ui| {
+                                if ui.button("Replay").clicked() {
+                                    self.start_replay(false);
+                                };
+
+                                if ui.button("Save replay").clicked() {
+                                    self.start_replay(true);
+                                };
+                            });
+                        });
+                    });
+
+                    ui.checkbox(&mut self.polish, "Polish with local search");
+
+                    if !self.final_cost.is_empty() {
+                        ui.label(format!("COST: {}", self.final_cost));
+                    }
+
+                    self.report.iter().for_each(|line| {
+                        ui.label(line);
+                    });
+                });
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let interaction_settings = &SettingsInteraction::new()
+                .with_dragging_enabled(true)
+                .with_node_clicking_enabled(true)
+                .with_node_selection_enabled(true)
+                .with_node_selection_multi_enabled(true)
+                .with_edge_clicking_enabled(true)
+                .with_edge_selection_enabled(true)
+                .with_edge_selection_multi_enabled(true);
+            let style_settings = &SettingsStyle::new().with_labels_always(true);
+            let navigation_settings = &SettingsNavigation::new()
+                .with_fit_to_screen_enabled(false)
+                .with_zoom_and_pan_enabled(true);
+
+            self.canvas =
+                ui
+                    .add(
+                        &mut egui_graphs::GraphView::<
+                            _,
+                            _,
+                            _,
+                            _,
+                            _,
+                            _,
+                            LayoutStateRandom,
+                            LayoutRandom,
+                        >::new(&mut self.g)
+                        .with_styles(style_settings)
+                        .with_interactions(interaction_settings)
+                        .with_navigations(navigation_settings),
+                    )
+                    .rect;
+        });
+    }
+}
+
+/// The graph view of the whole instance, with city names and distances as labels.
+fn view<Ty: EdgeType>(
+    graph: &StableGraph<String, (), Ty>,
+    nodes: &HashMap<NodeIndex, Node>,
+) -> View<Ty> {
+    let mut g = Graph::from(graph);
+
+    nodes.values().for_each(|node| {
+        g.node_mut(node.id).unwrap().set_label(node.name.clone());
+
+        node.neighbours.iter().for_each(|edge| {
+            g.edge_mut(edge.2.unwrap())
+                .unwrap()
+                .set_label(edge.1.to_string())
+        });
+    });
+
+    g
+}
+
+fn route_settings(ui: &mut egui::Ui, route: &mut Route, nodes: &HashMap<NodeIndex, Node>) {
+    let mut cities: Vec<(usize, &str)> = nodes
+        .values()
+        .map(|node| (node.id.index(), node.name.as_str()))
+        .collect();
+    cities.sort_by_key(|city| city.1);
+
+    let name = |idx: usize| nodes[&NodeIndex::new(idx)].name.clone();
+
+    let mut open = *route != Route::Closed;
+    ui.checkbox(&mut open, "Open path");
+
+    if !open {
+        *route = Route::Closed;
+        return;
+    }
+
+    let (mut start, mut end) = match *route {
+        Route::Open { start, end } => (start, end),
+        Route::Closed => (0, None),
+    };
+
+    egui::ComboBox::from_label("Start")
+        .selected_text(name(start))
+        .show_ui(ui, |ui| {
+            cities.iter().for_each(|&(idx, city)| {
+                ui.selectable_value(&mut start, idx, city);
+            });
+        });
+
+    egui::ComboBox::from_label("End")
+        .selected_text(end.map_or("Anywhere".to_string(), name))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut end, None, "Anywhere");
+            cities.iter().for_each(|&(idx, city)| {
+                ui.selectable_value(&mut end, Some(idx), city);
+            });
+        });
+
+    // A path can't end where it starts, so picking the start as the end means anywhere.
+    if end == Some(start) {
+        end = None;
+    }
+
+    *route = Route::Open { start, end };
+}
+
+fn depot_settings(ui: &mut egui::Ui, solver: &mut MultiSolver, nodes: &HashMap<NodeIndex, Node>) {
+    let mut cities: Vec<(usize, &str)> = nodes
+        .values()
+        .map(|node| (node.id.index(), node.name.as_str()))
+        .collect();
+    cities.sort_by_key(|city| city.1);
+
+    let mut separate = solver.depots.len() > 1;
+    ui.checkbox(&mut separate, "Depot per salesman");
+
+    let depots = if separate { solver.salesmen } else { 1 };
+    solver.depots.resize(depots, solver.depots[0]);
+
+    solver
+        .depots
+        .iter_mut()
+        .enumerate()
+        .for_each(|(salesman, depot)| {
+            let label = if separate {
+                format!("Depot {}", salesman + 1)
+            } else {
+                "Depot".to_string()
+            };
+
+            egui::ComboBox::new(format!("depot_{salesman}"), label)
+                .selected_text(nodes[&NodeIndex::new(*depot)].name.clone())
+                .show_ui(ui, |ui| {
+                    cities.iter().for_each(|&(idx, city)| {
+                        ui.selectable_value(depot, idx, city);
+                    });
+                });
+        });
+}
+
+fn ga_settings(ui: &mut egui::Ui, config: &mut GAConfig, id: &str) {
+    egui::ComboBox::new(format!("{id}_replacement"), "Replacement")
+        .selected_text(config.replacement.to_string())
+        .show_ui(ui, |ui| {
+            Replacement::ALL.into_iter().for_each(|replacement| {
+                ui.selectable_value(
+                    &mut config.replacement,
+                    replacement,
+                    replacement.to_string(),
+                );
+            });
+        });
+
+    ui.add(egui::Slider::new(&mut config.offspring_ratio, 0.1..=7.0).text("λ / μ"));
+    ui.add(egui::Slider::new(&mut config.memetic_rate, 0.0..=1.0).text("memetic rate"));
+    ui.add(egui::Slider::new(&mut config.diversity_threshold, 0.0..=1.0).text("min diversity"));
+    ui.checkbox(&mut config.reject_duplicates, "Reject duplicates");
+    ui.checkbox(&mut config.adaptive_operators, "Adaptive operators");
+    ui.checkbox(&mut config.self_adaptive_mutation, "Self-adaptive mutation");
+
+    egui::ComboBox::new(format!("{id}_schedule"), "Mutation schedule")
+        .selected_text(config.schedule.to_string())
+        .show_ui(ui, |ui| {
+            RateSchedule::ALL.into_iter().for_each(|schedule| {
+                ui.selectable_value(&mut config.schedule, schedule, schedule.to_string());
+            });
+        });
+
+    ui.horizontal(|ui| {
+        let mut fixed = config.seed.is_some();
+        let mut seed = config.seed.unwrap_or_default();
+
+        ui.checkbox(&mut fixed, "Seed");
+        ui.add_enabled(fixed, egui::DragValue::new(&mut seed));
+
+        config.seed = fixed.then_some(seed);
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Checkpoint");
+        ui.text_edit_singleline(&mut config.checkpoint_path);
+    });
+    ui.add(egui::Slider::new(&mut config.checkpoint_interval, 0..=10000).text("checkpoint every"));
+}
+
+/// Reads `City,Country,City,Country,distance` rows. A directed graph takes each row as the
+/// distance from the first city to the second and uses it both ways only when the reverse
+/// row is missing; an undirected graph always uses it both ways.
+fn load_graph<Ty: EdgeType>(data: &str) -> GraphTuple<Ty> {
+    let mut countries: HashMap<String, String> = HashMap::new();
+
+    let lines = fs::read_to_string(data)
+        .expect("Oops, could not open file.")
+        .lines()
+        .map(|line| {
+            let parsed = line.split(",").collect::<Vec<&str>>();
+
+            countries.insert(
+                parsed[0].to_owned() + ", " + parsed[1],
+                parsed[1].to_owned(),
+            );
+            countries.insert(
+                parsed[2].to_owned() + ", " + parsed[3],
+                parsed[3].to_owned(),
+            );
+
+            (
+                parsed[0].to_owned() + ", " + parsed[1],
+                parsed[2].to_owned() + ", " + parsed[3],
+                parsed[4].parse::<u32>().unwrap(),
+            )
+        })
+        .collect::<Vec<(String, String, u32)>>();
+
+    let listed: HashSet<(&str, &str)> = lines
+        .iter()
+        .map(|line| (line.0.as_str(), line.1.as_str()))
+        .collect();
+
+    let arcs: Vec<(String, String, u32)> = lines
+        .iter()
+        .flat_map(|line| {
+            let reverse = (line.1.clone(), line.0.clone(), line.2);
+            let mirrored = !Ty::is_directed() || !listed.contains(&(&line.1, &line.0));
+
+            [Some(line.clone()), mirrored.then_some(reverse)]
+        })
+        .flatten()
+        .collect();
+
+    let mut graph: StableGraph<String, (), Ty> = StableGraph::default();
+
+    // Cities are numbered in name order, so an index means the same city on every launch,
+    // as checkpoints and precedence pairs need.
+    let mut ids = arcs
+        .iter()
+        .flat_map(|line| [line.0.clone(), line.1.clone()])
+        .collect::<HashSet<String>>()
+        .into_iter()
+        .collect::<Vec<String>>();
+    ids.sort();
+
+    let mut node_map: HashMap<String, NodeIndex> = HashMap::new();
+
+    ids.iter().for_each(|city| {
+        let idx = graph.add_node(city.clone());
+        node_map.insert(city.clone(), idx);
+    });
+
+    let mut nodes = arcs.iter().fold(
+        HashMap::new(),
+        |mut acc, curr| -> HashMap<NodeIndex, Node> {
+            let curr_id = *node_map.get(&curr.0).unwrap();
+            let end_id = *node_map.get(&curr.1).unwrap();
+
+            acc.entry(curr_id)
+                .and_modify(|node| node.neighbours.push((end_id, curr.2, None)))
+                .or_insert(Node {
+                    id: curr_id,
+                    name: curr.0.clone(),
+                    country: countries[&curr.0].clone(),
+                    cluster: None,
+                    window: Window::default(),
+                    demand: 1,
+                    prize: 1,
+                    penalty: 1,
+                    position: None,
+                    neighbours: vec![(end_id, curr.2, None)],
+                });
 
             acc
         },
@@ -236,17 +1429,17 @@ fn load_graph() -> GraphTuple {
                 continue;
             }
 
-            if let Some(begin) = nodes.get_mut(&NodeIndex::new(i)) {
-                if let Some(found) = begin.neighbours.iter_mut().find(|n| n.0.index() == j) {
-                    let edge_idx = match graph.find_edge_undirected(begin.id, found.0) {
-                        Some(edge) => edge.0,
-                        None => graph.add_edge(begin.id, found.0, ()),
-                    };
+            if let Some(begin) = nodes.get_mut(&NodeIndex::new(i))
+                && let Some(found) = begin.neighbours.iter_mut().find(|n| n.0.index() == j)
+            {
+                let edge_idx = match graph.find_edge(begin.id, found.0) {
+                    Some(edge) => edge,
+                    None => graph.add_edge(begin.id, found.0, ()),
+                };
 
-                    found.2 = Some(edge_idx);
+                found.2 = Some(edge_idx);
 
-                    *val = found.1;
-                }
+                *val = found.1;
             }
         }
     }
@@ -254,11 +1447,642 @@ fn load_graph() -> GraphTuple {
     (graph, matrix, nodes)
 }
 
+/// Reads per-city attributes from rows that start with `City,Country`, after a header line
+/// naming the columns: any of `open`, `close`, `service`, `demand`, `prize`, `penalty`,
+/// `lat`, `lon` and `cluster`, in any order. Returns the first city listed, which is the
+/// depot. Cities and columns left out keep a window that is always open, no service time,
+/// a demand and prize of one, a penalty equal to the prize, no coordinates and their
+/// country as their cluster.
+fn load_cities(path: &str, nodes: &mut HashMap<NodeIndex, Node>) -> Option<NodeIndex> {
+    let by_name: HashMap<String, NodeIndex> = nodes
+        .values()
+        .map(|node| (node.name.clone(), node.id))
+        .collect();
+
+    let content = fs::read_to_string(path).expect("Oops, could not open cities file.");
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+
+    let header: Vec<String> = lines
+        .next()
+        .unwrap_or_default()
+        .split(",")
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|column| column == name);
+
+    let mut depot = None;
+
+    lines.for_each(|line| {
+        let parsed = line.split(",").collect::<Vec<&str>>();
+        let name = parsed[0].to_owned() + ", " + parsed[1];
+        let value = |name: &str| column(name).map(|idx| parsed[idx].trim().parse::<u32>().unwrap());
+        let coordinate =
+            |name: &str| column(name).map(|idx| parsed[idx].trim().parse::<f64>().unwrap());
+
+        let Some(&id) = by_name.get(&name) else {
+            println!("CITIES: {name} is not in the dataset");
+            return;
+        };
+
+        depot.get_or_insert(id);
+
+        let node = nodes.get_mut(&id).unwrap();
+        node.window.open = value("open").unwrap_or(node.window.open);
+        node.window.close = value("close").unwrap_or(node.window.close);
+        node.window.service = value("service").unwrap_or(node.window.service);
+        node.demand = value("demand").unwrap_or(node.demand);
+        node.prize = value("prize").unwrap_or(node.prize);
+        node.penalty = value("penalty").unwrap_or(node.prize);
+
+        if let (Some(lat), Some(lon)) = (coordinate("lat"), coordinate("lon")) {
+            node.position = Some((lat, lon));
+        }
+
+        if let Some(cluster) = column("cluster").map(|idx| parsed[idx].trim())
+            && !cluster.is_empty()
+        {
+            node.cluster = Some(cluster.to_string());
+        }
+    });
+
+    depot
+}
+
+/// Reads `City,Country,City,Country` rows, each asking for the first city to be visited
+/// before the second. Rows naming a city that is not in the dataset are skipped.
+fn load_precedence(path: &str, nodes: &HashMap<NodeIndex, Node>) -> Precedence {
+    let by_name: HashMap<String, usize> = nodes
+        .values()
+        .map(|node| (node.name.clone(), node.id.index()))
+        .collect();
+
+    let pairs: Vec<(usize, usize)> = fs::read_to_string(path)
+        .expect("Oops, could not open precedence file.")
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let parsed = line.split(",").collect::<Vec<&str>>();
+            let city = |idx: usize| {
+                let name = parsed[idx].to_owned() + ", " + parsed[idx + 1];
+                let city = by_name.get(&name).copied();
+
+                if city.is_none() {
+                    println!("PRECEDENCE: {name} is not in the dataset");
+                }
+
+                city
+            };
+
+            Some((city(0)?, city(2)?))
+        })
+        .collect();
+
+    let precedence = Precedence::new(pairs);
+
+    if !precedence.is_consistent(nodes.len()) {
+        println!("PRECEDENCE: pairs form a cycle, so no tour keeps them all");
+    }
+
+    // Closed tours start at city 0, the first city by name, so nothing comes before it.
+    let depot = &nodes[&NodeIndex::new(0)].name;
+    precedence
+        .pairs()
+        .iter()
+        .filter(|&&(_, after)| after == 0)
+        .for_each(|&(before, _)| {
+            println!(
+                "PRECEDENCE: closed tours start at {depot}, so none visits {} before it",
+                nodes[&NodeIndex::new(before)].name
+            )
+        });
+
+    precedence
+}
+
+/// Reads travel times in the rows of the distance file, mirrored the same way. Pairs
+/// without a row take their distance as the travel time.
+fn load_times<Ty: EdgeType>(
+    path: &str,
+    nodes: &HashMap<NodeIndex, Node>,
+    matrix: &[Vec<u32>],
+) -> Vec<Vec<u32>> {
+    let by_name: HashMap<String, usize> = nodes
+        .values()
+        .map(|node| (node.name.clone(), node.id.index()))
+        .collect();
+
+    let rows: Vec<(usize, usize, u32)> = fs::read_to_string(path)
+        .expect("Oops, could not open travel times file.")
+        .lines()
+        .map(|line| {
+            let parsed = line.split(",").collect::<Vec<&str>>();
+
+            (
+                by_name[&(parsed[0].to_owned() + ", " + parsed[1])],
+                by_name[&(parsed[2].to_owned() + ", " + parsed[3])],
+                parsed[4].parse::<u32>().unwrap(),
+            )
+        })
+        .collect();
+
+    let listed: HashSet<(usize, usize)> = rows.iter().map(|row| (row.0, row.1)).collect();
+    let mut times = matrix.to_vec();
+
+    rows.iter().for_each(|&(from, to, time)| {
+        times[from][to] = time;
+
+        if !Ty::is_directed() || !listed.contains(&(to, from)) {
+            times[to][from] = time;
+        }
+    });
+
+    times
+}
+
+/// Writes random instances of every size asked for, numbering the files by size when
+/// there is more than one.
+fn generate_headless(kind: &str, options: &[String]) {
+    let usage = || {
+        let kinds: Vec<String> = Kind::ALL
+            .iter()
+            .map(|kind| kind.to_string().to_lowercase().replace(' ', "-"))
+            .collect();
+
+        println!(
+            "USAGE: --generate <{}> [--size <n>[,<n>...]] [--seed <n>] [--width <km>] \
+             [--regions <n>] [--output <file>]",
+            kinds.join(" | ")
+        );
+    };
+
+    let Some(kind) = Kind::from_name(kind) else {
+        return usage();
+    };
+
+    let sizes = |value: &str| -> Option<Vec<usize>> {
+        value
+            .split(',')
+            .map(|size| size.trim().parse().ok().filter(|&size| size >= 2))
+            .collect()
+    };
+
+    let mut generator = Generator::new(kind, 0);
+    let mut counts = vec![100];
+    let mut output = "instance.txt".to_string();
+
+    for option in options.chunks(2) {
+        match option {
+            [flag, size] if flag == "--size" && sizes(size).is_some() => {
+                counts = sizes(size).unwrap_or_default()
+            }
+            [flag, seed] if flag == "--seed" && seed.parse::<u64>().is_ok() => {
+                generator.seed = seed.parse().ok()
+            }
+            [flag, width] if flag == "--width" && width.parse::<u32>().is_ok_and(|w| w > 0) => {
+                generator.width = width.parse().unwrap_or(generator.width)
+            }
+            [flag, regions] if flag == "--regions" && regions.parse::<usize>().is_ok() => {
+                generator.regions = regions.parse().unwrap_or(generator.regions)
+            }
+            [flag, file] if flag == "--output" => output = file.clone(),
+            _ => return usage(),
+        }
+    }
+
+    let output = Path::new(&output);
+
+    counts.iter().for_each(|&count| {
+        generator.cities = count;
+
+        let path = if counts.len() > 1 {
+            let stem = output.file_stem().and_then(|stem| stem.to_str());
+            let extension = output.extension().and_then(|extension| extension.to_str());
+
+            output.with_file_name(match (stem, extension) {
+                (Some(stem), Some(extension)) => format!("{stem}-{count}.{extension}"),
+                (stem, _) => format!("{}-{count}", stem.unwrap_or("instance")),
+            })
+        } else {
+            output.to_path_buf()
+        };
+
+        let instance = generator.generate();
+
+        match instance.save(&path) {
+            Ok(()) => println!(
+                "GENERATED: {} ({count} cities, {}, seed {})",
+                path.display(),
+                kind.to_string().to_lowercase(),
+                instance.seed
+            ),
+            Err(err) => println!("GENERATE FAILED: {err}"),
+        }
+    });
+}
+
+/// Continues a GA run from a checkpoint without opening the window, on the dataset the
+/// checkpoint was written for.
+fn resume_headless(path: &str) {
+    match Checkpoint::dataset(Path::new(path)) {
+        Ok((data, _)) if !Path::new(&data).is_file() => {
+            println!("RESUME FAILED: dataset `{data}` of the checkpoint not found")
+        }
+        Ok((data, true)) => resume_dataset::<Directed>(path, &data),
+        Ok((data, false)) => resume_dataset::<Undirected>(path, &data),
+        Err(err) => println!("RESUME FAILED: {err}"),
+    }
+}
+
+fn resume_dataset<Ty: EdgeType>(path: &str, data: &str) {
+    let (_, matrix, _) = load_graph::<Ty>(data);
+    let now = Instant::now();
+
+    let resumed = Checkpoint::load(Path::new(path), &matrix).and_then(|checkpoint| {
+        let config = GAConfig {
+            checkpoint_path: path.to_string(),
+            ..checkpoint.config.clone()
+        };
+
+        match checkpoint.strategy {
+            Strategy::Sequential => {
+                let mut solver = SequentialGASolver::new(matrix);
+                solver.config = config;
+                solver.resume(checkpoint)
+            }
+            Strategy::Parallel => {
+                let mut solver = ParallelGASolver::new(matrix);
+                solver.config = config;
+                solver.resume(checkpoint)
+            }
+            Strategy::Island { .. } => {
+                let mut solver = IslandGASolver::new(matrix);
+                solver.ga.config = config;
+                solver.resume(checkpoint)
+            }
+        }
+    });
+
+    match resumed {
+        Ok((cost, _)) => {
+            println!("ELAPSED: {}ms", now.elapsed().as_millis());
+            println!("COST: {cost}");
+        }
+        Err(err) => println!("RESUME FAILED: {err}"),
+    }
+}
+
+/// Checks and prices an itinerary without opening the window.
+fn evaluate_headless<Ty: EdgeType>(input: &Input, tour: &str) {
+    let (graph, matrix, mut nodes) = load_graph::<Ty>(&input.data);
+
+    if let Some(cities) = &input.cities {
+        load_cities(cities, &mut nodes);
+    }
+
+    let precedence = match &input.precedence {
+        Some(precedence) => load_precedence(precedence, &nodes),
+        None => Precedence::default(),
+    };
+
+    let text = fs::read_to_string(tour).unwrap_or_else(|_| tour.to_string());
+    let itinerary = Itinerary::new(&Itinerary::parse(&text), &nodes);
+
+    println!("{}", itinerary_cost(&itinerary));
+    itinerary_report(&itinerary, &nodes, &matrix, &precedence)
+        .iter()
+        .for_each(|line| println!("{line}"));
+
+    export_headless(
+        input,
+        itinerary.cost(),
+        &[itinerary.path],
+        &graph,
+        &nodes,
+        &matrix,
+    );
+}
+
+/// Solves with the portfolio and prints the tour without opening the window.
+fn solve_headless<Ty: EdgeType>(input: &Input) {
+    let (graph, matrix, mut nodes) = load_graph::<Ty>(&input.data);
+
+    if let Some(cities) = &input.cities {
+        load_cities(cities, &mut nodes);
+    }
+
+    let now = Instant::now();
+    let (cost, path) = PortfolioSolver::new(matrix.clone()).solve();
+
+    println!("ELAPSED: {}ms", now.elapsed().as_millis());
+    println!("COST: {cost}");
+
+    export_headless(input, cost, &[path], &graph, &nodes, &matrix);
+}
+
+/// Writes routes to the `--export` file, if one was given: as a solution, or as a figure
+/// laid out from the cities' coordinates or on a circle.
+fn export_headless<Ty: EdgeType>(
+    input: &Input,
+    cost: u32,
+    routes: &[Vec<usize>],
+    graph: &StableGraph<String, (), Ty>,
+    nodes: &HashMap<NodeIndex, Node>,
+    matrix: &[Vec<u32>],
+) {
+    let Some(export) = &input.export else {
+        return;
+    };
+
+    let path = Path::new(export);
+
+    let saved = match (Format::from_path(path), Figure::from_path(path)) {
+        (Some(format), _) => Solution {
+            cost,
+            routes,
+            nodes,
+            matrix,
+        }
+        .save(path, format),
+        (_, Some(Figure::Dot)) => fs::write(path, figure::dot(graph, matrix, routes)),
+        (_, Some(Figure::Svg)) => fs::write(
+            path,
+            Drawing::routes(nodes, matrix, routes, Ty::is_directed()).svg(),
+        ),
+        (None, None) => return,
+    };
+
+    match saved {
+        Ok(()) => println!("SAVED: {}", path.display()),
+        Err(err) => println!("SAVE FAILED: {err}"),
+    }
+}
+
+/// The itinerary's cost, which is only known when the dataset has every leg.
+fn itinerary_cost(itinerary: &Itinerary) -> String {
+    match itinerary.unknown_legs() {
+        0 => format!("COST: {}", itinerary.cost()),
+        unknown => format!("COST: unknown, {unknown} legs without a distance"),
+    }
+}
+
+/// The shape and legs of an itinerary, what keeps it from being a tour through every
+/// city, how far it is above the best tour of the same shape, and the precedence pairs it
+/// breaks.
+fn itinerary_report(
+    itinerary: &Itinerary,
+    nodes: &HashMap<NodeIndex, Node>,
+    matrix: &[Vec<u32>],
+    precedence: &Precedence,
+) -> Vec<String> {
+    let name = |city: usize| &nodes[&NodeIndex::new(city)].name;
+
+    let shape = match (itinerary.path.first(), itinerary.path.last()) {
+        (Some(&first), _) if itinerary.is_closed() => format!(
+            "TOUR: closed tour of {} cities from {}",
+            itinerary.cities(),
+            name(first)
+        ),
+        (Some(&first), Some(&last)) => format!(
+            "TOUR: open path of {} cities from {} to {}",
+            itinerary.cities(),
+            name(first),
+            name(last)
+        ),
+        _ => "TOUR: no known cities".to_string(),
+    };
+
+    let mut report = vec![shape];
+
+    report.extend(itinerary.legs.iter().map(|&(from, to, distance)| {
+        let distance = distance.map_or("no distance".to_string(), |d| d.to_string());
+        format!("LEG {} → {}: {distance}", name(from), name(to))
+    }));
+
+    report.extend(itinerary.problems.iter().map(|problem| problem.to_string()));
+
+    if !itinerary.is_valid() {
+        report.push(format!("INVALID: {} problems", itinerary.problems.len()));
+        return report;
+    }
+
+    report.push("VALID".to_string());
+
+    match best_known(matrix, itinerary.route()) {
+        Some((best, solver)) => {
+            let cost = itinerary.cost();
+            let gap = (cost as f64 - best as f64) / best.max(1) as f64 * 100.0;
+
+            report.push(format!("BEST KNOWN: {best} ({solver})"));
+            report.push(format!(
+                "GAP: {gap:+.2}% ({:+} km)",
+                cost as i64 - best as i64
+            ));
+        }
+        None => report.push("BEST KNOWN: none for open paths this large".to_string()),
+    }
+
+    if !precedence.is_empty() {
+        let violations = precedence.violations(&itinerary.path);
+
+        if violations.is_empty() {
+            report.push(format!(
+                "PRECEDENCE: all {} pairs kept",
+                precedence.pairs().len()
+            ));
+        }
+
+        report.extend(
+            violations.iter().map(|&(before, after)| {
+                format!("VIOLATED: {} before {}", name(before), name(after))
+            }),
+        );
+    }
+
+    report
+}
+
+/// Cost of the best tour in the given shape: optimal from Held-Karp when its table fits the
+/// portfolio's memory budget, otherwise Lin-Kernighan's for a closed tour.
+fn best_known(matrix: &[Vec<u32>], route: Route) -> Option<(u32, &'static str)> {
+    if DPSolver::memory(matrix.len()) <= DP_MEMORY_BUDGET {
+        let mut solver = DPSolver::new(matrix.to_vec());
+        solver.route = route;
+
+        return Some((solver.solve().0, "Held-Karp"));
+    }
+
+    match route {
+        Route::Closed => Some((
+            LinKernighanSolver::new(matrix.to_vec()).solve().0,
+            "Lin-Kernighan",
+        )),
+        Route::Open { .. } => None,
+    }
+}
+
+/// Routes vehicles of the given capacity with every construction and prints each set of
+/// routes without opening the window.
+fn route_vehicles_headless<Ty: EdgeType>(input: &Input, capacity: u32) {
+    let (graph, matrix, mut nodes) = load_graph::<Ty>(&input.data);
+    let depot = input
+        .cities
+        .as_ref()
+        .and_then(|cities| load_cities(cities, &mut nodes));
+
+    let (demands, names) = (0..nodes.len())
+        .map(|city| {
+            let node = &nodes[&NodeIndex::new(city)];
+            (node.demand, node.name.clone())
+        })
+        .unzip();
+
+    let mut solver = VehicleRoutingSolver::new(matrix.clone(), demands, names);
+    solver.depot = depot.map_or(0, |depot| depot.index());
+    solver.capacity = capacity;
+
+    let mut best: Option<(u32, Vec<Vec<usize>>)> = None;
+
+    Construction::ALL.into_iter().for_each(|construction| {
+        solver.construction = construction;
+        println!("{}", construction.to_string().to_uppercase());
+
+        let now = Instant::now();
+        let (cost, routes) = solver.solve();
+
+        println!("ELAPSED: {}ms", now.elapsed().as_millis());
+        println!("COST: {cost}");
+        vehicle_report(&solver, &routes, &nodes)
+            .iter()
+            .for_each(|line| println!("{line}"));
+
+        if !routes.is_empty() && best.as_ref().is_none_or(|best| cost < best.0) {
+            best = Some((cost, routes));
+        }
+    });
+
+    // The cheapest construction's routes are the ones exported.
+    if let Some((cost, routes)) = best {
+        export_headless(input, cost, &routes, &graph, &nodes, &matrix);
+    }
+}
+
+/// One line per vehicle: its colour in the window, load against capacity, distance and
+/// the cities in the order they are visited.
+fn vehicle_report(
+    solver: &VehicleRoutingSolver,
+    routes: &[Vec<usize>],
+    nodes: &HashMap<NodeIndex, Node>,
+) -> Vec<String> {
+    routes
+        .iter()
+        .enumerate()
+        .map(|(vehicle, route)| {
+            let (_, colour) = ROUTE_COLOURS[vehicle % ROUTE_COLOURS.len()];
+            let stops: Vec<&str> = route
+                .iter()
+                .map(|&city| nodes[&NodeIndex::new(city)].name.as_str())
+                .collect();
+
+            format!(
+                "VEHICLE {} ({colour}): load {}/{}, distance {}, {}",
+                vehicle + 1,
+                solver.load(route),
+                solver.capacity,
+                solver.length(route),
+                stops.join(" → "),
+            )
+        })
+        .collect()
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if let [flag, path] = args.as_slice()
+        && flag == "--resume"
+    {
+        return resume_headless(path);
+    }
+
+    if let [flag, kind, options @ ..] = args.as_slice()
+        && flag == "--generate"
+    {
+        return generate_headless(kind, options);
+    }
+
+    let mut input = Input::default();
+
+    for option in args.chunks(2) {
+        match option {
+            [flag, data] if flag == "--data" => input.data = data.clone(),
+            [flag, data] if flag == "--asymmetric" => {
+                input.data = data.clone();
+                input.directed = true;
+            }
+            [flag, cities] if flag == "--cities" => input.cities = Some(cities.clone()),
+            [flag, times] if flag == "--times" => input.times = Some(times.clone()),
+            [flag, precedence] if flag == "--precedence" => {
+                input.precedence = Some(precedence.clone())
+            }
+            [flag, tour] if flag == "--evaluate" => input.tour = Some(tour.clone()),
+            [flag, export]
+                if flag == "--export"
+                    && (Format::from_path(Path::new(export)).is_some()
+                        || Figure::from_path(Path::new(export)).is_some()) =>
+            {
+                input.export = Some(export.clone())
+            }
+            [flag, capacity] if flag == "--capacity" && capacity.parse::<u32>().is_ok() => {
+                input.capacity = capacity.parse().ok()
+            }
+            _ => {
+                println!(
+                    "USAGE: [--data <file> | --asymmetric <file>] [--cities <file>] \
+                     [--times <file>] [--precedence <file>] [--capacity <load>] \
+                     [--evaluate <tour>] [--export <file>] | --resume <checkpoint> | \
+                     --generate <kind> [options]"
+                );
+                return;
+            }
+        }
+    }
+
+    if let Some(tour) = &input.tour {
+        return if input.directed {
+            evaluate_headless::<Directed>(&input, tour)
+        } else {
+            evaluate_headless::<Undirected>(&input, tour)
+        };
+    }
+
+    if let Some(capacity) = input.capacity {
+        return if input.directed {
+            route_vehicles_headless::<Directed>(&input, capacity)
+        } else {
+            route_vehicles_headless::<Undirected>(&input, capacity)
+        };
+    }
+
+    if input.export.is_some() {
+        return if input.directed {
+            solve_headless::<Directed>(&input)
+        } else {
+            solve_headless::<Undirected>(&input)
+        };
+    }
+
+    if input.directed {
+        launch::<Directed>(input);
+    } else {
+        launch::<Undirected>(input);
+    }
+}
+
+fn launch<Ty: EdgeType + 'static>(input: Input) {
     run_native(
         "Pathfinder",
         NativeOptions::default(),
-        Box::new(|cc| Ok(Box::new(Pathfinder::new(cc)))),
+        Box::new(move |cc| Ok(Box::new(Pathfinder::<Ty>::new(cc, &input)))),
     )
     .unwrap();
 }
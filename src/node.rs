@@ -1,13 +1,30 @@
 use petgraph::graph::{EdgeIndex, NodeIndex};
 
+use crate::solvers::time_windows::Window;
+
 #[derive(PartialEq, Debug)]
 pub struct Node {
     pub id: NodeIndex,
     pub name: String,
+    pub country: String,
+    /// Group for the generalised TSP, when the cities file gives one.
+    pub cluster: Option<String>,
+    pub window: Window,
+    pub demand: u32,
+    pub prize: u32,
+    /// Charged for skipping the city in a prize-collecting tour.
+    pub penalty: u32,
+    /// Latitude and longitude, when the cities file gives them.
+    pub position: Option<(f64, f64)>,
     pub neighbours: Vec<(NodeIndex, u32, Option<EdgeIndex>)>,
 }
 
 impl Node {
+    /// The group the generalised TSP visits once: the cluster, or else the country.
+    pub fn group(&self) -> &str {
+        self.cluster.as_deref().unwrap_or(&self.country)
+    }
+
     pub fn get_edge_idxs(&self, filter_out: &[usize]) -> Vec<EdgeIndex> {
         self.neighbours
             .iter()
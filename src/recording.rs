@@ -0,0 +1,88 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use egui::{Color32, ColorImage};
+use png::{BitDepth, ColorType, Encoder};
+
+/// Most frames a replay is written with; longer runs are sampled evenly.
+pub const MAX_FRAMES: usize = 100;
+/// How long each frame of a replay is shown, in milliseconds.
+pub const FRAME_DELAY: u16 = 200;
+/// How long the last frame of a replay is held before it loops, in milliseconds.
+const FINAL_DELAY: u16 = 2000;
+
+pub fn save_png(path: &Path, image: &ColorImage) -> io::Result<()> {
+    let mut writer = encoder(path, image.size)?.write_header()?;
+    writer.write_image_data(&rgba(image, image.size))?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Writes the frames as an animated PNG that loops forever, every frame the size of the
+/// first.
+pub fn save_apng(path: &Path, frames: &[ColorImage]) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames"));
+    };
+
+    let mut encoder = encoder(path, first.size)?;
+    encoder.set_animated(frames.len() as u32, 0)?;
+    encoder.set_frame_delay(FRAME_DELAY, 1000)?;
+
+    let mut writer = encoder.write_header()?;
+
+    frames.iter().enumerate().try_for_each(|(frame, image)| {
+        if frame + 1 == frames.len() {
+            writer.set_frame_delay(FINAL_DELAY, 1000)?;
+        }
+
+        writer.write_image_data(&rgba(image, first.size))
+    })?;
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// At most `MAX_FRAMES` items spread evenly over `items`, always keeping the first and
+/// the last.
+pub fn sample<T: Clone>(items: &[T]) -> Vec<T> {
+    if items.len() <= MAX_FRAMES {
+        return items.to_vec();
+    }
+
+    (0..MAX_FRAMES)
+        .map(|step| items[step * (items.len() - 1) / (MAX_FRAMES - 1)].clone())
+        .collect()
+}
+
+fn encoder(
+    path: &Path,
+    [width, height]: [usize; 2],
+) -> io::Result<Encoder<'static, BufWriter<File>>> {
+    let mut encoder = Encoder::new(
+        BufWriter::new(File::create(path)?),
+        width as u32,
+        height as u32,
+    );
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    Ok(encoder)
+}
+
+/// The image's pixels as unmultiplied RGBA bytes, cropped or padded with transparency
+/// to `size` so a window resized during a replay still fits the animation.
+fn rgba(image: &ColorImage, [width, height]: [usize; 2]) -> Vec<u8> {
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let pixel = if x < image.size[0] && y < image.size[1] {
+                image.pixels[y * image.size[0] + x]
+            } else {
+                Color32::TRANSPARENT
+            };
+
+            pixel.to_srgba_unmultiplied()
+        })
+        .collect()
+}
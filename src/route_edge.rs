@@ -0,0 +1,73 @@
+use egui::epaint::ColorMode;
+use egui::{Color32, Pos2, Shape};
+use egui_graphs::{DefaultEdgeShape, DisplayEdge, DisplayNode, DrawContext, EdgeProps, Node};
+use petgraph::EdgeType;
+use petgraph::stable_graph::IndexType;
+
+/// Colours told apart on the dark theme, with the names used in the console.
+pub const ROUTE_COLOURS: [(Color32, &str); 8] = [
+    (Color32::RED, "red"),
+    (Color32::GREEN, "green"),
+    (Color32::LIGHT_BLUE, "blue"),
+    (Color32::GOLD, "gold"),
+    (Color32::MAGENTA, "magenta"),
+    (Color32::ORANGE, "orange"),
+    (Color32::CYAN, "cyan"),
+    (Color32::KHAKI, "khaki"),
+];
+
+/// Default edge drawn in the colour of the route it belongs to, when it has one.
+#[derive(Clone, Debug)]
+pub struct RouteEdge {
+    shape: DefaultEdgeShape,
+    pub colour: Option<Color32>,
+}
+
+impl<E: Clone> From<EdgeProps<E>> for RouteEdge {
+    fn from(edge: EdgeProps<E>) -> Self {
+        Self {
+            shape: DefaultEdgeShape::from(edge),
+            colour: None,
+        }
+    }
+}
+
+impl<N: Clone, E: Clone, Ty: EdgeType, Ix: IndexType, D: DisplayNode<N, E, Ty, Ix>>
+    DisplayEdge<N, E, Ty, Ix, D> for RouteEdge
+{
+    fn shapes(
+        &mut self,
+        start: &Node<N, E, Ty, Ix, D>,
+        end: &Node<N, E, Ty, Ix, D>,
+        ctx: &DrawContext,
+    ) -> Vec<Shape> {
+        let mut shapes = DisplayEdge::shapes(&mut self.shape, start, end, ctx);
+
+        if let Some(colour) = self.colour {
+            shapes.iter_mut().for_each(|shape| match shape {
+                Shape::LineSegment { stroke, .. } => stroke.color = colour,
+                Shape::CubicBezier(curve) => curve.stroke.color = ColorMode::Solid(colour),
+                Shape::Path(tip) => {
+                    tip.fill = colour;
+                    tip.stroke.color = ColorMode::Solid(colour);
+                }
+                _ => {}
+            });
+        }
+
+        shapes
+    }
+
+    fn update(&mut self, state: &EdgeProps<E>) {
+        DisplayEdge::<N, E, Ty, Ix, D>::update(&mut self.shape, state);
+    }
+
+    fn is_inside(
+        &self,
+        start: &Node<N, E, Ty, Ix, D>,
+        end: &Node<N, E, Ty, Ix, D>,
+        pos: Pos2,
+    ) -> bool {
+        self.shape.is_inside(start, end, pos)
+    }
+}
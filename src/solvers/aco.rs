@@ -0,0 +1,261 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::Rng;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::local_search::LocalSearchSolver;
+use super::tour;
+
+const ANTS: usize = 32;
+const ITERATIONS: usize = 500;
+const CANDIDATES: usize = 15;
+const EXPLOITATION: f64 = 0.9;
+const LOCAL_EVAPORATION: f64 = 0.1;
+const MMAS_BEST_PROBABILITY: f64 = 0.05;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum AntVariant {
+    Basic,
+    MaxMin,
+    ColonySystem,
+}
+
+impl AntVariant {
+    pub const ALL: [AntVariant; 3] = [
+        AntVariant::Basic,
+        AntVariant::MaxMin,
+        AntVariant::ColonySystem,
+    ];
+}
+
+impl fmt::Display for AntVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AntVariant::Basic => "Ant System",
+            AntVariant::MaxMin => "MAX-MIN Ant System",
+            AntVariant::ColonySystem => "Ant Colony System",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+pub struct AntColonySolver {
+    matrix: Vec<Vec<u32>>,
+    neighbours: Vec<Vec<usize>>,
+    local_search: LocalSearchSolver,
+    symmetric: bool,
+    pub variant: AntVariant,
+    pub alpha: f64,
+    pub beta: f64,
+    pub evaporation: f64,
+    pub two_opt: bool,
+}
+
+impl AntColonySolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        Self {
+            neighbours: tour::candidate_lists(&matrix, CANDIDATES),
+            local_search: LocalSearchSolver::new(matrix.clone()),
+            symmetric: tour::is_symmetric(&matrix),
+            matrix,
+            variant: AntVariant::MaxMin,
+            alpha: 1.0,
+            beta: 3.0,
+            evaporation: 0.1,
+            two_opt: false,
+        }
+    }
+}
+
+impl AntColonySolver {
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        self.solve_until(&AtomicBool::new(false), &|_, _| {})
+    }
+
+    /// The colony's best tour once every iteration ran, or as soon as `stop` is set.
+    /// `improved` is told about every new best tour as soon as it is found.
+    pub fn solve_until(
+        &self,
+        stop: &AtomicBool,
+        improved: &(dyn Fn(u32, &[usize]) + Sync),
+    ) -> (u32, Vec<usize>) {
+        let len = self.matrix.len();
+
+        if len < 3 {
+            let path = tour::nearest_neighbour(&self.matrix, 0);
+            return (tour::cost(&self.matrix, &path), path);
+        }
+
+        let greedy = tour::cost(&self.matrix, &tour::nearest_neighbour(&self.matrix, 0)).max(1);
+
+        let initial = match self.variant {
+            AntVariant::Basic => ANTS as f64 / greedy as f64,
+            AntVariant::MaxMin => 1.0 / (self.evaporation * greedy as f64),
+            AntVariant::ColonySystem => 1.0 / (len as f64 * greedy as f64),
+        };
+
+        let mut pheromone = vec![vec![initial; len]; len];
+        let mut best: (u32, Vec<usize>) = (u32::MAX, vec![]);
+
+        for _ in 0..ITERATIONS {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let choice: Vec<Vec<f64>> = (0..len)
+                .map(|i| {
+                    (0..len)
+                        .map(|j| {
+                            let heuristic = 1.0 / (self.matrix[i][j] as f64).max(0.1);
+                            pheromone[i][j].powf(self.alpha) * heuristic.powf(self.beta)
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let ants: Vec<(u32, Vec<usize>)> = (0..ANTS)
+                .into_par_iter()
+                .map(|_| {
+                    let path = self.construct(&choice);
+
+                    if self.two_opt {
+                        self.local_search.improve(&path)
+                    } else {
+                        (tour::cost(&self.matrix, &path), path)
+                    }
+                })
+                .collect();
+
+            let iteration_best = ants.iter().min_by_key(|ant| ant.0).unwrap().clone();
+
+            if iteration_best.0 < best.0 {
+                best = iteration_best.clone();
+                improved(best.0, &best.1);
+            }
+
+            match self.variant {
+                AntVariant::Basic => {
+                    self.evaporate(&mut pheromone, self.evaporation);
+                    ants.iter().for_each(|(cost, path)| {
+                        self.deposit(&mut pheromone, path, 1.0 / *cost as f64)
+                    });
+                }
+                AntVariant::MaxMin => {
+                    self.evaporate(&mut pheromone, self.evaporation);
+                    self.deposit(
+                        &mut pheromone,
+                        &iteration_best.1,
+                        1.0 / iteration_best.0 as f64,
+                    );
+
+                    let max = 1.0 / (self.evaporation * best.0 as f64);
+                    let root = MMAS_BEST_PROBABILITY.powf(1.0 / len as f64);
+                    let min = max * (1.0 - root) / ((len as f64 / 2.0 - 1.0).max(1.0) * root);
+
+                    pheromone
+                        .iter_mut()
+                        .flatten()
+                        .for_each(|level| *level = level.clamp(min, max));
+                }
+                AntVariant::ColonySystem => {
+                    // Local updates are applied once every ant has finished, since the ants
+                    // walk in parallel.
+                    ants.iter().for_each(|(_, path)| {
+                        self.update(&mut pheromone, path, |level| {
+                            (1.0 - LOCAL_EVAPORATION) * level + LOCAL_EVAPORATION * initial
+                        })
+                    });
+
+                    self.update(&mut pheromone, &best.1, |level| {
+                        (1.0 - self.evaporation) * level + self.evaporation / best.0 as f64
+                    });
+                }
+            }
+        }
+
+        best
+    }
+
+    fn evaporate(&self, pheromone: &mut [Vec<f64>], rate: f64) {
+        pheromone
+            .iter_mut()
+            .flatten()
+            .for_each(|level| *level *= 1.0 - rate);
+    }
+
+    fn deposit(&self, pheromone: &mut [Vec<f64>], path: &[usize], amount: f64) {
+        self.update(pheromone, path, |level| level + amount);
+    }
+
+    /// Sets the pheromone on every edge of `path`, and on its reverse when the instance is
+    /// symmetric, to `updated` of its current level.
+    fn update(&self, pheromone: &mut [Vec<f64>], path: &[usize], updated: impl Fn(f64) -> f64) {
+        path.windows(2).for_each(|pair| {
+            let level = updated(pheromone[pair[0]][pair[1]]);
+
+            pheromone[pair[0]][pair[1]] = level;
+
+            if self.symmetric {
+                pheromone[pair[1]][pair[0]] = level;
+            }
+        });
+    }
+
+    /// Walks one ant from a random city, choosing among the nearest unvisited cities in
+    /// proportion to pheromone and closeness. Returns a closed tour starting at city 0.
+    fn construct(&self, choice: &[Vec<f64>]) -> Vec<usize> {
+        let len = self.matrix.len();
+        let mut rng = rand::rng();
+        let mut visited = vec![false; len];
+        let mut order = Vec::with_capacity(len + 1);
+
+        let mut current = rng.random_range(0..len);
+        visited[current] = true;
+        order.push(current);
+
+        while order.len() < len {
+            let open: Vec<usize> = self.neighbours[current]
+                .iter()
+                .copied()
+                .filter(|&city| !visited[city])
+                .collect();
+
+            let greedy =
+                self.variant == AntVariant::ColonySystem && rng.random::<f64>() < EXPLOITATION;
+
+            let next = if open.is_empty() || greedy {
+                let pool: Vec<usize> = if open.is_empty() {
+                    (0..len).filter(|&city| !visited[city]).collect()
+                } else {
+                    open
+                };
+
+                pool.into_iter()
+                    .max_by(|&a, &b| choice[current][a].total_cmp(&choice[current][b]))
+                    .unwrap()
+            } else {
+                let total: f64 = open.iter().map(|&city| choice[current][city]).sum();
+                let mut target = rng.random::<f64>() * total;
+
+                *open
+                    .iter()
+                    .find(|&&city| {
+                        target -= choice[current][city];
+                        target <= 0.0
+                    })
+                    .unwrap_or(open.last().unwrap())
+            };
+
+            visited[next] = true;
+            order.push(next);
+            current = next;
+        }
+
+        let start = order.iter().position(|&city| city == 0).unwrap();
+        order.rotate_left(start);
+        order.push(0);
+        order
+    }
+}
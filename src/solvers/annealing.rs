@@ -0,0 +1,248 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::{Rng, seq::SliceRandom};
+
+use super::tour;
+
+const STEPS_PER_CITY: usize = 50_000;
+const FINAL_TEMPERATURE_RATIO: f64 = 1e-4;
+const REHEAT_RATIO: f64 = 0.3;
+const REHEAT_STALL: usize = 20;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum CoolingSchedule {
+    Geometric,
+    Linear,
+    LundyMees,
+    Reheating,
+}
+
+impl CoolingSchedule {
+    pub const ALL: [CoolingSchedule; 4] = [
+        CoolingSchedule::Geometric,
+        CoolingSchedule::Linear,
+        CoolingSchedule::LundyMees,
+        CoolingSchedule::Reheating,
+    ];
+}
+
+impl fmt::Display for CoolingSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CoolingSchedule::Geometric => "Geometric",
+            CoolingSchedule::Linear => "Linear",
+            CoolingSchedule::LundyMees => "Lundy-Mees",
+            CoolingSchedule::Reheating => "Reheating",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+enum Move {
+    TwoOpt,
+    Swap,
+    Insertion,
+}
+
+pub struct SimulatedAnnealingSolver {
+    matrix: Vec<Vec<u32>>,
+    symmetric: bool,
+    pub cooling: CoolingSchedule,
+}
+
+impl SimulatedAnnealingSolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        Self {
+            symmetric: tour::is_symmetric(&matrix),
+            matrix,
+            cooling: CoolingSchedule::Geometric,
+        }
+    }
+}
+
+impl SimulatedAnnealingSolver {
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        self.solve_until(&AtomicBool::new(false), &|_, _| {})
+    }
+
+    /// The best tour of the annealing run, cut short when `stop` is set. `improved` is
+    /// told about every new best tour as soon as it is found.
+    pub fn solve_until(
+        &self,
+        stop: &AtomicBool,
+        improved: &(dyn Fn(u32, &[usize]) + Sync),
+    ) -> (u32, Vec<usize>) {
+        let len = self.matrix.len();
+        let mut rng = rand::rng();
+
+        let mut path: Vec<usize> = vec![0];
+        let mut cities: Vec<usize> = (1..len).collect();
+        cities.shuffle(&mut rng);
+        path.append(&mut cities);
+        path.push(0);
+
+        let mut cost = tour::cost(&self.matrix, &path) as i64;
+
+        if len < 4 {
+            return (cost as u32, path);
+        }
+
+        let mut best = (cost, path.clone());
+
+        let steps = len * STEPS_PER_CITY;
+        let initial = self.initial_temperature(&mut path.clone(), &mut rng);
+        let last = initial * FINAL_TEMPERATURE_RATIO;
+
+        let ratio = (last / initial).powf(1.0 / steps as f64);
+        let beta = (initial - last) / (steps as f64 * initial * last);
+
+        let mut temperature = initial;
+        let mut stall = 0;
+
+        for step in 0..steps {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let applied = self.random_move(&mut path, &mut rng);
+            let delta = applied.delta;
+
+            if delta <= 0 || rng.random::<f64>() < (-(delta as f64) / temperature).exp() {
+                cost += delta;
+
+                if cost < best.0 {
+                    best = (cost, path.clone());
+                    improved(cost as u32, &path);
+                    stall = 0;
+                }
+            } else {
+                applied.undo(&mut path);
+            }
+
+            stall += 1;
+
+            temperature = match self.cooling {
+                CoolingSchedule::Geometric => temperature * ratio,
+                CoolingSchedule::Linear => initial - (initial - last) * step as f64 / steps as f64,
+                CoolingSchedule::LundyMees => temperature / (1.0 + beta * temperature),
+                CoolingSchedule::Reheating if stall > steps / REHEAT_STALL => {
+                    stall = 0;
+                    temperature.max(initial * REHEAT_RATIO)
+                }
+                CoolingSchedule::Reheating => temperature * ratio,
+            };
+        }
+
+        (best.0 as u32, best.1)
+    }
+
+    /// Picks the starting temperature so that an average uphill move is accepted with
+    /// probability one half.
+    fn initial_temperature(&self, path: &mut [usize], rng: &mut impl Rng) -> f64 {
+        let uphill: Vec<i64> = (0..100)
+            .map(|_| {
+                let applied = self.random_move(path, rng);
+                applied.undo(path);
+                applied.delta
+            })
+            .filter(|&delta| delta > 0)
+            .collect();
+
+        if uphill.is_empty() {
+            return 1.0;
+        }
+
+        let average = uphill.iter().sum::<i64>() as f64 / uphill.len() as f64;
+
+        average / 2f64.ln()
+    }
+
+    fn random_move(&self, path: &mut [usize], rng: &mut impl Rng) -> Applied {
+        let len = self.matrix.len();
+
+        let (i, j) = loop {
+            let i = rng.random_range(1..len);
+            let j = rng.random_range(1..len);
+
+            if i != j {
+                break (i.min(j), i.max(j));
+            }
+        };
+
+        let kind = match rng.random_range(0..3) {
+            0 => Move::TwoOpt,
+            1 => Move::Swap,
+            _ => Move::Insertion,
+        };
+
+        let forward = rng.random::<bool>();
+
+        // Edge `k` joins positions `k` and `k + 1`. A reversed segment only keeps the cost
+        // of its inner edges on symmetric instances.
+        let (before, after): (Vec<usize>, Vec<usize>) = match kind {
+            Move::TwoOpt if self.symmetric => (vec![i - 1, j], vec![i - 1, j]),
+            Move::TwoOpt => ((i - 1..=j).collect(), (i - 1..=j).collect()),
+            Move::Swap if j == i + 1 => (vec![i - 1, i, j], vec![i - 1, i, j]),
+            Move::Swap => (vec![i - 1, i, j - 1, j], vec![i - 1, i, j - 1, j]),
+            Move::Insertion if forward => (vec![i - 1, i, j], vec![i - 1, j - 1, j]),
+            Move::Insertion => (vec![i - 1, j - 1, j], vec![i - 1, i, j]),
+        };
+
+        let edges = |path: &[usize], edges: &[usize]| -> i64 {
+            edges
+                .iter()
+                .map(|&k| self.matrix[path[k]][path[k + 1]] as i64)
+                .sum()
+        };
+
+        let removed = edges(path, &before);
+
+        let applied = Applied {
+            kind,
+            i,
+            j,
+            forward,
+            delta: 0,
+        };
+        applied.apply(path);
+
+        Applied {
+            delta: edges(path, &after) - removed,
+            ..applied
+        }
+    }
+}
+
+struct Applied {
+    kind: Move,
+    i: usize,
+    j: usize,
+    forward: bool,
+    delta: i64,
+}
+
+impl Applied {
+    fn apply(&self, path: &mut [usize]) {
+        let (i, j) = (self.i, self.j);
+
+        match self.kind {
+            Move::TwoOpt => path[i..=j].reverse(),
+            Move::Swap => path.swap(i, j),
+            Move::Insertion if self.forward => path[i..=j].rotate_left(1),
+            Move::Insertion => path[i..=j].rotate_right(1),
+        }
+    }
+
+    fn undo(&self, path: &mut [usize]) {
+        let (i, j) = (self.i, self.j);
+
+        match self.kind {
+            Move::TwoOpt => path[i..=j].reverse(),
+            Move::Swap => path.swap(i, j),
+            Move::Insertion if self.forward => path[i..=j].rotate_right(1),
+            Move::Insertion => path[i..=j].rotate_left(1),
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use std::f64::consts::TAU;
+use std::fmt;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::local_search::LocalSearchSolver;
+use super::tour;
+
+const ELITISM: usize = 2;
+const TOURNAMENT: usize = 3;
+const CROSSOVER_RATE: f64 = 0.9;
+const MUTATION_RATE: f64 = 0.3;
+const POWER_ITERATIONS: usize = 200;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Construction {
+    Savings,
+    Sweep,
+    /// Route first, cluster second: a GA over giant tours that are split optimally into
+    /// vehicle routes.
+    GiantTour,
+}
+
+impl Construction {
+    pub const ALL: [Construction; 3] = [
+        Construction::Savings,
+        Construction::Sweep,
+        Construction::GiantTour,
+    ];
+}
+
+impl fmt::Display for Construction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Construction::Savings => write!(f, "Clarke–Wright savings"),
+            Construction::Sweep => write!(f, "Sweep"),
+            Construction::GiantTour => write!(f, "Giant tour split (GA)"),
+        }
+    }
+}
+
+/// Capacitated vehicle routing: closed routes from the depot that together visit every
+/// city once, none of them carrying more than the vehicle capacity.
+pub struct VehicleRoutingSolver {
+    matrix: Vec<Vec<u32>>,
+    demands: Vec<u32>,
+    names: Vec<String>,
+    local_search: LocalSearchSolver,
+    symmetric: bool,
+    pub depot: usize,
+    pub capacity: u32,
+    pub construction: Construction,
+    pub population: usize,
+    pub generations: usize,
+}
+
+impl VehicleRoutingSolver {
+    /// `names[city]` is what the city is called in messages.
+    pub fn new(matrix: Vec<Vec<u32>>, demands: Vec<u32>, names: Vec<String>) -> Self {
+        Self {
+            local_search: LocalSearchSolver::new(matrix.clone()),
+            symmetric: tour::is_symmetric(&matrix),
+            matrix,
+            demands,
+            names,
+            depot: 0,
+            capacity: 10,
+            construction: Construction::Savings,
+            population: 100,
+            generations: 300,
+        }
+    }
+}
+
+impl VehicleRoutingSolver {
+    /// Returns the total distance and one closed route per vehicle, each polished with
+    /// local search. No routes are returned when a single city outweighs the capacity.
+    pub fn solve(&self) -> (u32, Vec<Vec<usize>>) {
+        let customers = self.customers();
+
+        if let Some(&city) = customers
+            .iter()
+            .find(|&&city| self.demands[city] > self.capacity)
+        {
+            println!(
+                "CVRP: {} needs {} but a vehicle carries {}",
+                self.names[city], self.demands[city], self.capacity
+            );
+            return (0, vec![]);
+        }
+
+        let routes: Vec<Vec<usize>> = match self.construction {
+            Construction::Savings => self.savings(&customers),
+            Construction::Sweep => self.sweep(&customers),
+            Construction::GiantTour => self.giant_tour(customers),
+        }
+        .iter()
+        .map(|route| self.local_search.improve(route).1)
+        .collect();
+
+        let total = routes.iter().map(|route| self.length(route)).sum();
+
+        println!("CVRP: {} vehicles, total {total}", routes.len());
+
+        (total, routes)
+    }
+
+    pub fn length(&self, route: &[usize]) -> u32 {
+        tour::cost(&self.matrix, route)
+    }
+
+    pub fn load(&self, route: &[usize]) -> u32 {
+        route
+            .iter()
+            .filter(|&&city| city != self.depot)
+            .map(|&city| self.demands[city])
+            .sum()
+    }
+
+    fn customers(&self) -> Vec<usize> {
+        (0..self.matrix.len())
+            .filter(|&city| city != self.depot)
+            .collect()
+    }
+
+    fn close(&self, cities: &[usize]) -> Vec<usize> {
+        let mut route = Vec::with_capacity(cities.len() + 2);
+        route.push(self.depot);
+        route.extend_from_slice(cities);
+        route.push(self.depot);
+        route
+    }
+
+    /// Parallel Clarke–Wright: starts with one route per city and keeps joining the end of
+    /// one route to the start of another, largest saving first, while the load fits.
+    fn savings(&self, customers: &[usize]) -> Vec<Vec<usize>> {
+        let d = |from: usize, to: usize| self.matrix[from][to] as i64;
+        let n = self.matrix.len();
+
+        let mut routes: Vec<Option<Vec<usize>>> =
+            customers.iter().map(|&city| Some(vec![city])).collect();
+        let mut loads: Vec<u32> = customers.iter().map(|&city| self.demands[city]).collect();
+        let mut route_of = vec![usize::MAX; n];
+        customers
+            .iter()
+            .enumerate()
+            .for_each(|(route, &city)| route_of[city] = route);
+
+        let mut savings: Vec<(i64, usize, usize)> = customers
+            .iter()
+            .flat_map(|&i| customers.iter().map(move |&j| (i, j)))
+            .filter(|&(i, j)| i != j)
+            .map(|(i, j)| (d(i, self.depot) + d(self.depot, j) - d(i, j), i, j))
+            .filter(|saving| saving.0 > 0)
+            .collect();
+
+        savings.sort_by_key(|saving| std::cmp::Reverse(saving.0));
+
+        for (_, i, j) in savings {
+            let (a, b) = (route_of[i], route_of[j]);
+
+            if a == b || loads[a] + loads[b] > self.capacity {
+                continue;
+            }
+
+            // Routes may be walked backwards on symmetric instances, so `i` and `j` only
+            // have to be ends of their routes.
+            if self.symmetric {
+                let head = routes[a].as_mut().unwrap();
+
+                if head.first() == Some(&i) {
+                    head.reverse();
+                }
+
+                let tail = routes[b].as_mut().unwrap();
+
+                if tail.last() == Some(&j) {
+                    tail.reverse();
+                }
+            }
+
+            let joinable = routes[a].as_ref().unwrap().last() == Some(&i)
+                && routes[b].as_ref().unwrap().first() == Some(&j);
+
+            if !joinable {
+                continue;
+            }
+
+            let tail = routes[b].take().unwrap();
+            tail.iter().for_each(|&city| route_of[city] = a);
+            routes[a].as_mut().unwrap().extend(tail);
+            loads[a] += loads[b];
+        }
+
+        routes
+            .into_iter()
+            .flatten()
+            .map(|cities| self.close(&cities))
+            .collect()
+    }
+
+    /// Sorts the cities by their angle around the depot and fills one vehicle after another
+    /// along that order, trying every city as the first one and keeping the shortest set.
+    fn sweep(&self, customers: &[usize]) -> Vec<Vec<usize>> {
+        let points = self.coordinates();
+        let (x0, y0) = points[self.depot];
+
+        let mut order = customers.to_vec();
+        order.sort_by(|&a, &b| {
+            let angle = |city: usize| {
+                let (x, y) = points[city];
+                (y - y0).atan2(x - x0).rem_euclid(TAU)
+            };
+
+            angle(a).total_cmp(&angle(b))
+        });
+
+        (0..order.len().max(1))
+            .map(|first| {
+                let mut routes: Vec<Vec<usize>> = vec![];
+                let mut current: Vec<usize> = vec![];
+                let mut load = 0;
+
+                order[first..]
+                    .iter()
+                    .chain(&order[..first])
+                    .for_each(|&city| {
+                        if load + self.demands[city] > self.capacity {
+                            routes.push(self.close(&std::mem::take(&mut current)));
+                            load = 0;
+                        }
+
+                        current.push(city);
+                        load += self.demands[city];
+                    });
+
+                if !current.is_empty() {
+                    routes.push(self.close(&current));
+                }
+
+                routes
+            })
+            .min_by_key(|routes| routes.iter().map(|route| self.length(route)).sum::<u32>())
+            .unwrap()
+    }
+
+    /// Places the cities in the plane by classical multidimensional scaling of the distance
+    /// matrix, since the input has no coordinates.
+    fn coordinates(&self) -> Vec<(f64, f64)> {
+        let n = self.matrix.len();
+        let squared = |i: usize, j: usize| {
+            let d = (self.matrix[i][j] as f64 + self.matrix[j][i] as f64) / 2.;
+            d * d
+        };
+
+        let means: Vec<f64> = (0..n)
+            .map(|i| (0..n).map(|j| squared(i, j)).sum::<f64>() / n as f64)
+            .collect();
+        let mean = means.iter().sum::<f64>() / n as f64;
+
+        let mut gram: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| -0.5 * (squared(i, j) - means[i] - means[j] + mean))
+                    .collect()
+            })
+            .collect();
+
+        // The two leading eigenvectors by power iteration, removing the first before
+        // finding the second.
+        let axes: Vec<Vec<f64>> = (0..2)
+            .map(|axis| {
+                let mut v: Vec<f64> = (0..n).map(|i| ((i + axis) % 3) as f64 + 1.).collect();
+                let mut value = 0.;
+
+                for _ in 0..POWER_ITERATIONS {
+                    let next: Vec<f64> = gram
+                        .iter()
+                        .map(|row| row.iter().zip(&v).map(|(a, b)| a * b).sum())
+                        .collect();
+                    let norm = next.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+                    if norm == 0. {
+                        break;
+                    }
+
+                    value = norm;
+                    v = next.iter().map(|x| x / norm).collect();
+                }
+
+                gram.iter_mut().enumerate().for_each(|(i, row)| {
+                    row.iter_mut()
+                        .enumerate()
+                        .for_each(|(j, entry)| *entry -= value * v[i] * v[j]);
+                });
+
+                v.iter().map(|x| x * value.sqrt()).collect()
+            })
+            .collect();
+
+        (0..n).map(|city| (axes[0][city], axes[1][city])).collect()
+    }
+
+    /// Memetic GA over orders of all cities. Each order is cut into routes by `split`, the
+    /// routes are polished, and the polished routes joined back into the order.
+    fn giant_tour(&self, customers: Vec<usize>) -> Vec<Vec<usize>> {
+        if customers.is_empty() {
+            return vec![];
+        }
+
+        let mut rng = rand::rng();
+
+        let mut population: Vec<(u32, Vec<usize>)> = (0..self.population.max(ELITISM + 1))
+            .map(|_| {
+                let mut order = customers.clone();
+                order.shuffle(&mut rng);
+                self.evaluate(order)
+            })
+            .collect();
+
+        population.sort();
+
+        for generation in 0..self.generations {
+            let mut next: Vec<(u32, Vec<usize>)> = population[..ELITISM].to_vec();
+
+            while next.len() < population.len() {
+                let parent_1 = tournament(&population, &mut rng);
+                let parent_2 = tournament(&population, &mut rng);
+
+                let mut order = if rng.random::<f64>() < CROSSOVER_RATE {
+                    tour::order_crossover(parent_1, parent_2, &mut rng)
+                } else {
+                    parent_1.to_vec()
+                };
+
+                if rng.random::<f64>() < MUTATION_RATE && order.len() > 2 {
+                    let (i, j) = (
+                        rng.random_range(0..order.len()),
+                        rng.random_range(0..order.len()),
+                    );
+                    order[i.min(j)..=i.max(j)].reverse();
+                }
+
+                next.push(self.evaluate(order));
+            }
+
+            next.sort();
+            population = next;
+
+            if generation % 50 == 0 {
+                println!("GENERATION {generation}: {}", population[0].0);
+            }
+        }
+
+        self.split(&population[0].1)
+    }
+
+    fn evaluate(&self, order: Vec<usize>) -> (u32, Vec<usize>) {
+        let routes: Vec<Vec<usize>> = self
+            .split(&order)
+            .iter()
+            .map(|route| self.local_search.improve(route).1)
+            .collect();
+
+        let order = routes
+            .iter()
+            .flat_map(|route| &route[1..route.len() - 1])
+            .copied()
+            .collect();
+
+        (routes.iter().map(|route| self.length(route)).sum(), order)
+    }
+
+    /// Prins' split: the cheapest way to cut a giant tour into consecutive capacity-feasible
+    /// routes, as a shortest path over the cut positions.
+    fn split(&self, order: &[usize]) -> Vec<Vec<usize>> {
+        let d = |from: usize, to: usize| self.matrix[from][to];
+        let len = order.len();
+
+        let mut best = vec![u32::MAX; len + 1];
+        let mut cut = vec![0; len + 1];
+        best[0] = 0;
+
+        for i in 0..len {
+            let mut load = 0;
+            let mut cost = 0;
+
+            for j in i..len {
+                load += self.demands[order[j]];
+
+                if load > self.capacity {
+                    break;
+                }
+
+                cost = if j == i {
+                    d(self.depot, order[i]) + d(order[i], self.depot)
+                } else {
+                    cost - d(order[j - 1], self.depot)
+                        + d(order[j - 1], order[j])
+                        + d(order[j], self.depot)
+                };
+
+                if best[i] + cost < best[j + 1] {
+                    best[j + 1] = best[i] + cost;
+                    cut[j + 1] = i;
+                }
+            }
+        }
+
+        let mut routes = vec![];
+        let mut end = len;
+
+        while end > 0 {
+            routes.push(self.close(&order[cut[end]..end]));
+            end = cut[end];
+        }
+
+        routes.reverse();
+        routes
+    }
+}
+
+fn tournament<'a>(population: &'a [(u32, Vec<usize>)], rng: &mut impl Rng) -> &'a [usize] {
+    &(0..TOURNAMENT)
+        .map(|_| &population[rng.random_range(0..population.len())])
+        .min_by_key(|individual| individual.0)
+        .unwrap()
+        .1
+}
@@ -1,30 +1,82 @@
-use std::{cmp::min, collections::VecDeque};
+use std::{
+    cmp::min,
+    collections::VecDeque,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+use super::precedence::Precedence;
+use super::route::Route;
 
 pub struct DPSolver {
     matrix: Vec<Vec<u32>>,
+    pub route: Route,
+    pub precedence: Precedence,
 }
 
 impl DPSolver {
     pub fn new(matrix: Vec<Vec<u32>>) -> Self {
-        Self { matrix }
+        Self {
+            matrix,
+            route: Route::Closed,
+            precedence: Precedence::default(),
+        }
     }
 }
 
 impl DPSolver {
+    /// Bytes the DP table needs for `cities` cities.
+    pub fn memory(cities: usize) -> usize {
+        1usize
+            .checked_shl(cities as u32)
+            .and_then(|size| {
+                size.checked_mul(cities * size_of::<usize>() + size_of::<Vec<usize>>())
+            })
+            .unwrap_or(usize::MAX)
+    }
+
     pub fn solve(&self) -> (u32, Vec<usize>) {
+        self.solve_bounded(&AtomicU32::new(u32::MAX), &AtomicBool::new(false))
+            .unwrap_or_else(|| {
+                if !self.precedence.is_empty() {
+                    println!("HELD-KARP: no tour keeps every precedence pair");
+                }
+
+                (0, vec![])
+            })
+    }
+
+    /// Held-Karp that never extends a partial tour already costing at least `bound`, which
+    /// other solvers may lower while this runs. Returns the optimal tour if it is cheaper
+    /// than the final bound; `None` proves that no tour beats the bound, unless `stop` was
+    /// set and the search abandoned. A city is only added once every city it has to
+    /// follow is in the tour.
+    pub fn solve_bounded(&self, bound: &AtomicU32, stop: &AtomicBool) -> Option<(u32, Vec<usize>)> {
         let n = self.matrix.len();
         let size = 1 << n;
+        let start = self.route.start();
+        let required = self.precedence.required(n);
+
+        // Nothing can come before the start city.
+        if required[start] != 0 {
+            return None;
+        }
 
         let mut dp = vec![vec![usize::MAX; n]; size];
-        dp[1][0] = 0;
+        dp[1 << start][start] = 0;
 
         for mask in 1..size {
+            if stop.load(Ordering::Relaxed) {
+                return None;
+            }
+
+            let limit = bound.load(Ordering::Relaxed) as usize;
+
             for u in 0..n {
-                if (mask & (1 << u)) == 0 {
+                if (mask & (1 << u)) == 0 || dp[mask][u] >= limit {
                     continue;
                 }
                 for v in 0..n {
-                    if (mask & (1 << v)) != 0 || u == v {
+                    if (mask & (1 << v)) != 0 || u == v || required[v] & !mask != 0 {
                         continue;
                     }
                     let next_mask = mask | (1 << v);
@@ -38,10 +90,15 @@ impl DPSolver {
 
         let full_mask = (1 << n) - 1;
         let mut result = usize::MAX;
-        let mut last_city = 0;
+        let mut last_city = start;
 
-        for u in 1..n {
-            let cost = dp[full_mask][u].saturating_add(self.matrix[u][0] as usize);
+        for (u, &reached) in dp[full_mask].iter().enumerate() {
+            let cost = match self.route {
+                Route::Closed if u == start => continue,
+                Route::Closed => reached.saturating_add(self.matrix[u][start] as usize),
+                Route::Open { end: Some(end), .. } if u != end => continue,
+                Route::Open { .. } => reached,
+            };
 
             if cost < result {
                 result = cost;
@@ -49,14 +106,21 @@ impl DPSolver {
             }
         }
 
+        if result >= bound.load(Ordering::Relaxed) as usize {
+            return None;
+        }
+
         let mut tour_rev = VecDeque::with_capacity(n + 1);
         let mut mask = full_mask;
         let mut city = last_city;
 
-        tour_rev.push_front(0);
+        if self.route == Route::Closed {
+            tour_rev.push_front(start);
+        }
+
         tour_rev.push_front(city);
 
-        while mask != 1 {
+        while mask != 1 << start {
             let prev_mask = mask ^ (1 << city);
 
             let mut prev_city = 0;
@@ -76,6 +140,6 @@ impl DPSolver {
             mask = prev_mask;
         }
 
-        (result as u32, tour_rev.into_iter().collect())
+        Some((result as u32, tour_rev.into_iter().collect()))
     }
 }
@@ -0,0 +1,251 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use super::chromosome::Chromosome;
+use super::config::GAConfig;
+use super::operators::{CrossoverOperator, MutationOperator};
+use super::replacement::Family;
+
+const MIN_PROBABILITY: f64 = 0.1;
+const QUALITY_RATE: f64 = 0.3;
+const PURSUIT_RATE: f64 = 0.3;
+const MUTATION_STEP: f64 = 0.2;
+const MIN_MUTATION_RATE: f32 = 0.01;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum RateSchedule {
+    Constant,
+    Linear,
+    Exponential,
+}
+
+impl RateSchedule {
+    pub const ALL: [RateSchedule; 3] = [
+        RateSchedule::Constant,
+        RateSchedule::Linear,
+        RateSchedule::Exponential,
+    ];
+
+    /// Multiplier for the configured mutation rate after `progress` (0 to 1) of the run.
+    pub fn factor(&self, progress: f64) -> f32 {
+        let factor = match self {
+            RateSchedule::Constant => 1.0,
+            RateSchedule::Linear => 1.0 - 0.9 * progress,
+            RateSchedule::Exponential => 0.1f64.powf(progress),
+        };
+
+        factor as f32
+    }
+}
+
+impl fmt::Display for RateSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RateSchedule::Constant => "Constant",
+            RateSchedule::Linear => "Linear decay",
+            RateSchedule::Exponential => "Exponential decay",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Adaptive pursuit over a fixed set of operators: each operator keeps a running estimate
+/// of the improvement it produces, and the best one is pursued towards the largest
+/// probability while every other operator keeps at least `MIN_PROBABILITY`.
+#[derive(Clone)]
+pub struct Pursuit {
+    pub probability: Vec<f64>,
+    pub quality: Vec<f64>,
+    pub uses: Vec<usize>,
+    pub improvements: Vec<usize>,
+}
+
+impl Pursuit {
+    fn new(operators: usize) -> Self {
+        Self {
+            probability: vec![1.0 / operators as f64; operators],
+            quality: vec![0.0; operators],
+            uses: vec![0; operators],
+            improvements: vec![0; operators],
+        }
+    }
+
+    fn choose(&self, rng: &mut StdRng) -> usize {
+        let mut target = rng.random::<f64>();
+
+        self.probability
+            .iter()
+            .position(|&probability| {
+                target -= probability;
+                target <= 0.0
+            })
+            .unwrap_or(self.probability.len() - 1)
+    }
+
+    fn reward(&mut self, operator: usize, reward: f64) {
+        let operators = self.probability.len();
+        let max_probability = 1.0 - (operators - 1) as f64 * MIN_PROBABILITY;
+
+        self.uses[operator] += 1;
+        if reward > 0.0 {
+            self.improvements[operator] += 1;
+        }
+
+        self.quality[operator] += QUALITY_RATE * (reward - self.quality[operator]);
+
+        let best = (0..operators)
+            .max_by(|&a, &b| self.quality[a].total_cmp(&self.quality[b]))
+            .unwrap();
+
+        self.probability
+            .iter_mut()
+            .enumerate()
+            .for_each(|(idx, probability)| {
+                let target = if idx == best {
+                    max_probability
+                } else {
+                    MIN_PROBABILITY
+                };
+
+                *probability += PURSUIT_RATE * (target - *probability);
+            });
+    }
+
+    fn summary(&self) -> Vec<String> {
+        (0..self.probability.len())
+            .map(|idx| {
+                format!(
+                    "p={:.2}, used {}, improved {}",
+                    self.probability[idx], self.uses[idx], self.improvements[idx]
+                )
+            })
+            .collect()
+    }
+}
+
+/// Improvement an operator produced while breeding one family, credited once the whole
+/// generation has been bred so parallel breeding stays deterministic.
+pub enum Credit {
+    Crossover(CrossoverOperator, f64),
+    Mutation(MutationOperator, f64),
+}
+
+impl Credit {
+    pub fn crossover(operator: CrossoverOperator, before: u32, after: u32) -> Self {
+        Credit::Crossover(operator, improvement(before, after))
+    }
+
+    pub fn mutation(operator: MutationOperator, before: u32, after: u32) -> Self {
+        Credit::Mutation(operator, improvement(before, after))
+    }
+}
+
+/// Operator choice and credit assignment for one population.
+#[derive(Clone)]
+pub struct OperatorControl {
+    adaptive: bool,
+    self_adaptive: bool,
+    pub crossover: Pursuit,
+    pub mutation: Pursuit,
+}
+
+impl OperatorControl {
+    pub fn new(config: &GAConfig) -> Self {
+        Self {
+            adaptive: config.adaptive_operators,
+            self_adaptive: config.self_adaptive_mutation,
+            crossover: Pursuit::new(CrossoverOperator::ALL.len()),
+            mutation: Pursuit::new(MutationOperator::ALL.len()),
+        }
+    }
+
+    pub fn crossover(&self, rng: &mut StdRng) -> CrossoverOperator {
+        if !self.adaptive {
+            return CrossoverOperator::Exchange;
+        }
+
+        CrossoverOperator::ALL[self.crossover.choose(rng)]
+    }
+
+    pub fn mutation(&self, rng: &mut StdRng) -> MutationOperator {
+        if !self.adaptive {
+            return MutationOperator::Swap;
+        }
+
+        MutationOperator::ALL[self.mutation.choose(rng)]
+    }
+
+    pub fn record(&mut self, families: &[Family]) {
+        if !self.adaptive {
+            return;
+        }
+
+        families
+            .iter()
+            .flat_map(|family| &family.credits)
+            .for_each(|credit| match *credit {
+                Credit::Crossover(operator, reward) => {
+                    let idx = CrossoverOperator::ALL
+                        .iter()
+                        .position(|&other| other == operator)
+                        .unwrap();
+
+                    self.crossover.reward(idx, reward);
+                }
+                Credit::Mutation(operator, reward) => {
+                    let idx = MutationOperator::ALL
+                        .iter()
+                        .position(|&other| other == operator)
+                        .unwrap();
+
+                    self.mutation.reward(idx, reward);
+                }
+            });
+    }
+
+    pub fn report(&self, population: &[Chromosome]) {
+        if self.adaptive {
+            CrossoverOperator::ALL
+                .iter()
+                .map(|operator| operator.to_string())
+                .zip(self.crossover.summary())
+                .chain(
+                    MutationOperator::ALL
+                        .iter()
+                        .map(|operator| operator.to_string())
+                        .zip(self.mutation.summary()),
+                )
+                .for_each(|(name, summary)| println!("OPERATOR: {name} {summary}"));
+        }
+
+        if self.self_adaptive && !population.is_empty() {
+            let mean: f32 = population
+                .iter()
+                .map(|individual| individual.mutation_rate)
+                .sum::<f32>()
+                / population.len() as f32;
+
+            println!("MUTATION RATE: {mean:.3}");
+        }
+    }
+}
+
+fn improvement(before: u32, after: u32) -> f64 {
+    if after >= before || before == 0 {
+        0.0
+    } else {
+        (before - after) as f64 / before as f64
+    }
+}
+
+/// Log-normal self-adaptation of an inherited mutation rate.
+pub fn perturb(rate: f32, rng: &mut StdRng) -> f32 {
+    let (u_1, u_2) = (1.0 - rng.random::<f64>(), rng.random::<f64>());
+    let normal = (-2.0 * u_1.ln()).sqrt() * (2.0 * PI * u_2).cos();
+
+    (rate * (MUTATION_STEP * normal).exp() as f32).clamp(MIN_MUTATION_RATE, 1.0)
+}
@@ -0,0 +1,457 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::str::FromStr;
+
+use super::adaptive::{OperatorControl, Pursuit, RateSchedule};
+use super::chromosome::Chromosome;
+use super::config::{GAConfig, Improvement};
+use super::island::MigrationTopology;
+use super::operators::{CrossoverOperator, MutationOperator};
+use super::replacement::Replacement;
+use crate::solvers::precedence::Precedence;
+use crate::solvers::route::Route;
+
+const HEADER: &str = "pathfinder-ga 2";
+const NOT_A_CHECKPOINT: &str = "not a pathfinder GA checkpoint, or one from an older version";
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Strategy {
+    Sequential,
+    Parallel,
+    Island {
+        migration_interval: usize,
+        migration_size: usize,
+        topology: MigrationTopology,
+    },
+}
+
+/// Complete state of a GA run between two generations. Every random draw of a generation
+/// comes from streams derived from `seed` and the generation number, so resuming from a
+/// checkpoint continues exactly as the uninterrupted run would have.
+pub struct Checkpoint {
+    pub strategy: Strategy,
+    pub config: GAConfig,
+    pub seed: u64,
+    pub generation: usize,
+    pub best: Option<Chromosome>,
+    pub populations: Vec<Vec<Chromosome>>,
+    pub controls: Vec<OperatorControl>,
+}
+
+impl Checkpoint {
+    pub fn new(
+        strategy: Strategy,
+        config: &GAConfig,
+        seed: u64,
+        populations: Vec<Vec<Chromosome>>,
+    ) -> Self {
+        let mut checkpoint = Self {
+            strategy,
+            config: config.clone(),
+            seed,
+            generation: 0,
+            best: None,
+            controls: vec![OperatorControl::new(config); populations.len()],
+            populations,
+        };
+
+        checkpoint.update_best(config);
+        checkpoint
+    }
+
+    /// Configuration to resume with: everything the run depends on comes from the
+    /// checkpoint, while where and how often to write further checkpoints and where to
+    /// report progress stays `current`. Fails when `current` solves another dataset.
+    pub fn resumed_config(&self, current: &GAConfig) -> io::Result<GAConfig> {
+        if !current.data.is_empty()
+            && (current.data != self.config.data || current.directed != self.config.directed)
+        {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("checkpoint was written for {}", self.instance()),
+            ));
+        }
+
+        Ok(GAConfig {
+            checkpoint_path: current.checkpoint_path.clone(),
+            checkpoint_interval: current.checkpoint_interval,
+            progress: current.progress.clone(),
+            ..self.config.clone()
+        })
+    }
+
+    /// The dataset the run solves, as the console reports it.
+    pub fn instance(&self) -> String {
+        if self.config.directed {
+            format!("{} (directed)", self.config.data)
+        } else {
+            self.config.data.clone()
+        }
+    }
+
+    /// Records the best tour after the generations since `previous` and writes the
+    /// checkpoint whenever the run crosses a multiple of the checkpoint interval.
+    pub fn advance(&mut self, config: &GAConfig, previous: usize) {
+        self.update_best(config);
+
+        let interval = config.checkpoint_interval;
+
+        if interval == 0 || config.checkpoint_path.is_empty() {
+            return;
+        }
+
+        if self.generation / interval > previous / interval {
+            match self.save(Path::new(&config.checkpoint_path)) {
+                Ok(()) => println!("CHECKPOINT: generation {}", self.generation),
+                Err(err) => println!("CHECKPOINT FAILED: {err}"),
+            }
+        }
+    }
+
+    pub fn result(&self) -> (u32, Vec<usize>) {
+        match &self.best {
+            Some(val) => (val.fitness, val.gnome.clone()),
+            None => (0, vec![]),
+        }
+    }
+
+    /// Keeps the fittest individual seen so far and reports it to `config.progress` when
+    /// it improves.
+    fn update_best(&mut self, config: &GAConfig) {
+        let minimum = self
+            .populations
+            .iter()
+            .flatten()
+            .min_by(|x, y| x.fitness.cmp(&y.fitness));
+
+        if let Some(minimum) = minimum
+            && self
+                .best
+                .as_ref()
+                .is_none_or(|best| minimum.fitness < best.fitness)
+        {
+            self.best = Some(minimum.clone());
+
+            // The receiver may be gone; the run goes on without it.
+            if let Some(progress) = &config.progress {
+                let _ = progress.send(Improvement {
+                    generation: self.generation,
+                    cost: minimum.fitness,
+                    tour: minimum.gnome.clone(),
+                });
+            }
+        }
+    }
+
+    /// Writes the checkpoint next to `path` first and then moves it into place, so an
+    /// interrupted write never destroys the previous checkpoint.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let config = &self.config;
+
+        let strategy = match self.strategy {
+            Strategy::Sequential => "sequential".to_string(),
+            Strategy::Parallel => "parallel".to_string(),
+            Strategy::Island {
+                migration_interval,
+                migration_size,
+                topology,
+            } => format!("island {migration_interval} {migration_size} {topology}"),
+        };
+
+        let mut lines = vec![
+            HEADER.to_string(),
+            format!("strategy {strategy}"),
+            format!("seed {}", self.seed),
+            format!("generation {}", self.generation),
+            format!("data {}", config.data),
+            format!("directed {}", config.directed),
+            format!("population {}", config.population),
+            format!("generations {}", config.generations),
+            format!("elitism {}", config.elitism),
+            format!("crossover_rate {}", config.crossover_rate),
+            format!("mutation_rate {}", config.mutation_rate),
+            format!("memetic_rate {}", config.memetic_rate),
+            format!("reject_duplicates {}", config.reject_duplicates),
+            format!("diversity_threshold {}", config.diversity_threshold),
+            format!("immigration_rate {}", config.immigration_rate),
+            format!("adaptive_operators {}", config.adaptive_operators),
+            format!("self_adaptive_mutation {}", config.self_adaptive_mutation),
+            format!("schedule {}", config.schedule),
+            format!("replacement {}", config.replacement),
+            format!("offspring_ratio {}", config.offspring_ratio),
+            format!("route {}", config.route),
+            format!("precedence {}", config.precedence),
+            format!("checkpoint_interval {}", config.checkpoint_interval),
+        ];
+
+        if let Some(best) = &self.best {
+            lines.push(format!("best {}", individual(best)));
+        }
+
+        self.populations
+            .iter()
+            .zip(&self.controls)
+            .enumerate()
+            .for_each(|(idx, (population, control))| {
+                lines.push(format!("island {idx}"));
+                lines.push(format!("crossover {}", pursuit(&control.crossover)));
+                lines.push(format!("mutation {}", pursuit(&control.mutation)));
+
+                population.iter().for_each(|chromosome| {
+                    lines.push(format!("individual {}", individual(chromosome)));
+                });
+            });
+
+        lines.push(String::new());
+
+        let staging = path.with_extension("tmp");
+        fs::write(&staging, lines.join("\n"))?;
+        fs::rename(staging, path)
+    }
+
+    /// The dataset file and directedness a checkpoint was written for, to load the
+    /// instance before the checkpoint itself.
+    pub fn dataset(path: &Path) -> io::Result<(String, bool)> {
+        let text = fs::read_to_string(path)?;
+        let mut lines = text.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err(invalid(NOT_A_CHECKPOINT));
+        }
+
+        let mut dataset = (String::new(), false);
+
+        for line in lines {
+            match line.split_once(' ') {
+                Some(("data", value)) => dataset.0 = value.to_string(),
+                Some(("directed", value)) => dataset.1 = parse(value)?,
+                _ => {}
+            }
+        }
+
+        Ok(dataset)
+    }
+
+    /// Reads a checkpoint written by [`Checkpoint::save`] for the instance in `matrix`.
+    /// Stored fitness values are ignored and every tour is priced again.
+    pub fn load(path: &Path, matrix: &[Vec<u32>]) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let mut lines = text.lines();
+        let cities = matrix.len();
+
+        if lines.next() != Some(HEADER) {
+            return Err(invalid(NOT_A_CHECKPOINT));
+        }
+
+        let mut config = GAConfig::default();
+        let mut strategy = None;
+        let mut seed = None;
+        let mut generation = 0;
+        let mut best = None;
+        let mut populations: Vec<Vec<Chromosome>> = vec![];
+        let mut controls: Vec<OperatorControl> = vec![];
+
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+
+            match key {
+                "strategy" => strategy = Some(parse_strategy(value)?),
+                "seed" => seed = Some(parse(value)?),
+                "generation" => generation = parse(value)?,
+                "data" => config.data = value.to_string(),
+                "directed" => config.directed = parse(value)?,
+                "population" => config.population = parse(value)?,
+                "generations" => config.generations = parse(value)?,
+                "elitism" => config.elitism = parse(value)?,
+                "crossover_rate" => config.crossover_rate = parse(value)?,
+                "mutation_rate" => config.mutation_rate = parse(value)?,
+                "memetic_rate" => config.memetic_rate = parse(value)?,
+                "reject_duplicates" => config.reject_duplicates = parse(value)?,
+                "diversity_threshold" => config.diversity_threshold = parse(value)?,
+                "immigration_rate" => config.immigration_rate = parse(value)?,
+                "adaptive_operators" => config.adaptive_operators = parse(value)?,
+                "self_adaptive_mutation" => config.self_adaptive_mutation = parse(value)?,
+                "schedule" => config.schedule = parse_named(value, RateSchedule::ALL)?,
+                "replacement" => config.replacement = parse_named(value, Replacement::ALL)?,
+                "offspring_ratio" => config.offspring_ratio = parse(value)?,
+                "route" => config.route = parse_route(value)?,
+                "precedence" => config.precedence = parse_precedence(value, cities)?,
+                "checkpoint_interval" => config.checkpoint_interval = parse(value)?,
+                "best" => best = Some(parse_individual(value, &config, matrix)?),
+                "island" => {
+                    populations.push(vec![]);
+                    controls.push(OperatorControl::new(&config));
+                }
+                "crossover" | "mutation" | "individual" if populations.is_empty() => {
+                    return Err(invalid("population data before the first island"));
+                }
+                "crossover" => {
+                    controls.last_mut().unwrap().crossover =
+                        parse_pursuit(value, CrossoverOperator::ALL.len())?
+                }
+                "mutation" => {
+                    controls.last_mut().unwrap().mutation =
+                        parse_pursuit(value, MutationOperator::ALL.len())?
+                }
+                "individual" => populations
+                    .last_mut()
+                    .unwrap()
+                    .push(parse_individual(value, &config, matrix)?),
+                "" => {}
+                _ => return Err(invalid(&format!("unknown checkpoint entry `{key}`"))),
+            }
+        }
+
+        let seed = seed.ok_or_else(|| invalid("checkpoint has no seed"))?;
+        config.seed = Some(seed);
+
+        if populations.iter().any(|population| population.is_empty()) {
+            return Err(invalid("checkpoint has an empty population"));
+        }
+
+        Ok(Self {
+            strategy: strategy.ok_or_else(|| invalid("checkpoint has no strategy"))?,
+            config,
+            seed,
+            generation,
+            best,
+            populations,
+            controls,
+        })
+    }
+}
+
+fn individual(chromosome: &Chromosome) -> String {
+    let gnome: Vec<String> = chromosome.gnome.iter().map(usize::to_string).collect();
+
+    format!(
+        "{} {} {}",
+        chromosome.fitness,
+        chromosome.mutation_rate,
+        gnome.join(" ")
+    )
+}
+
+fn pursuit(pursuit: &Pursuit) -> String {
+    let values: Vec<String> = pursuit
+        .probability
+        .iter()
+        .chain(&pursuit.quality)
+        .map(f64::to_string)
+        .chain(
+            pursuit
+                .uses
+                .iter()
+                .chain(&pursuit.improvements)
+                .map(usize::to_string),
+        )
+        .collect();
+
+    values.join(" ")
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+fn parse<T: FromStr>(value: &str) -> io::Result<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid(&format!("invalid checkpoint value `{value}`")))
+}
+
+fn parse_named<T: ToString + Copy, const N: usize>(value: &str, all: [T; N]) -> io::Result<T> {
+    all.into_iter()
+        .find(|candidate| candidate.to_string() == value)
+        .ok_or_else(|| invalid(&format!("unknown checkpoint setting `{value}`")))
+}
+
+fn parse_strategy(value: &str) -> io::Result<Strategy> {
+    let mut parts = value.splitn(4, ' ');
+
+    match parts.next() {
+        Some("sequential") => Ok(Strategy::Sequential),
+        Some("parallel") => Ok(Strategy::Parallel),
+        Some("island") => Ok(Strategy::Island {
+            migration_interval: parse(parts.next().unwrap_or(""))?,
+            migration_size: parse(parts.next().unwrap_or(""))?,
+            topology: parse_named(parts.next().unwrap_or(""), MigrationTopology::ALL)?,
+        }),
+        _ => Err(invalid(&format!("unknown strategy `{value}`"))),
+    }
+}
+
+fn parse_route(value: &str) -> io::Result<Route> {
+    let mut parts = value.split(' ');
+
+    match parts.next() {
+        Some("closed") => Ok(Route::Closed),
+        Some("open") => Ok(Route::Open {
+            start: parse(parts.next().unwrap_or(""))?,
+            end: parts.next().map(parse).transpose()?,
+        }),
+        _ => Err(invalid(&format!("unknown route `{value}`"))),
+    }
+}
+
+/// Pairs written as `before>after`, separated by spaces.
+fn parse_precedence(value: &str, cities: usize) -> io::Result<Precedence> {
+    let pairs = value
+        .split_whitespace()
+        .map(|pair| {
+            let (before, after) = pair
+                .split_once('>')
+                .ok_or_else(|| invalid(&format!("invalid precedence pair `{pair}`")))?;
+            let pair: (usize, usize) = (parse(before)?, parse(after)?);
+
+            if pair.0 >= cities || pair.1 >= cities {
+                return Err(invalid("precedence pair does not match this instance"));
+            }
+
+            Ok(pair)
+        })
+        .collect::<io::Result<Vec<(usize, usize)>>>()?;
+
+    Ok(Precedence::new(pairs))
+}
+
+fn parse_individual(value: &str, config: &GAConfig, matrix: &[Vec<u32>]) -> io::Result<Chromosome> {
+    let mut parts = value.split(' ');
+    let _: u32 = parse(parts.next().unwrap_or(""))?;
+    let mutation_rate = parse(parts.next().unwrap_or(""))?;
+    let gnome = parts.map(parse).collect::<io::Result<Vec<usize>>>()?;
+
+    if !config.route.is_valid(&gnome, matrix.len()) {
+        return Err(invalid("checkpoint tour does not match this instance"));
+    }
+
+    if !config.precedence.violations(&gnome).is_empty() {
+        return Err(invalid("checkpoint tour breaks a precedence pair"));
+    }
+
+    Ok(Chromosome {
+        fitness: Chromosome::fitness(&gnome, matrix),
+        gnome,
+        mutation_rate,
+    })
+}
+
+fn parse_pursuit(value: &str, operators: usize) -> io::Result<Pursuit> {
+    let values: Vec<&str> = value.split(' ').collect();
+
+    if values.len() != 4 * operators {
+        return Err(invalid("malformed operator statistics"));
+    }
+
+    let floats = |part: &[&str]| part.iter().map(|v| parse(v)).collect::<io::Result<_>>();
+    let counts = |part: &[&str]| part.iter().map(|v| parse(v)).collect::<io::Result<_>>();
+
+    Ok(Pursuit {
+        probability: floats(&values[..operators])?,
+        quality: floats(&values[operators..2 * operators])?,
+        uses: counts(&values[2 * operators..3 * operators])?,
+        improvements: counts(&values[3 * operators..])?,
+    })
+}
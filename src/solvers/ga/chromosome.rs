@@ -1,20 +1,26 @@
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::solvers::local_search::LocalSearchSolver;
+use crate::solvers::route::Route;
 
 #[derive(Debug, Clone)]
 pub struct Chromosome {
     pub gnome: Vec<usize>,
     pub fitness: u32,
+    pub mutation_rate: f32,
 }
 
 impl Ord for Chromosome {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.fitness.cmp(&self.fitness)
+        self.fitness.cmp(&other.fitness)
     }
 }
 
 impl PartialOrd for Chromosome {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(other.cmp(self))
+        Some(self.cmp(other))
     }
 }
 
@@ -30,7 +36,48 @@ impl Chromosome {
     pub fn new(matrix: &[Vec<u32>], gnome: Vec<usize>) -> Self {
         let fitness = Self::fitness(&gnome, matrix);
 
-        Self { gnome, fitness }
+        Self {
+            gnome,
+            fitness,
+            mutation_rate: 0.0,
+        }
+    }
+
+    /// Hash of the solution. A closed tour hashes the same from any starting city and,
+    /// unless `directed`, in either direction; an open path hashes as it is.
+    pub fn identity(&self, route: Route, directed: bool) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        if route != Route::Closed {
+            self.gnome.hash(&mut hasher);
+            return hasher.finish();
+        }
+
+        let cycle = match self.gnome.as_slice() {
+            [first, .., last] if first == last => &self.gnome[..self.gnome.len() - 1],
+            _ => &self.gnome[..],
+        };
+
+        if let Some(start) = cycle.iter().enumerate().min_by_key(|(_, city)| **city) {
+            let len = cycle.len();
+            let forward = (0..len).map(|step| cycle[(start.0 + step) % len]);
+            let backward = (0..len).map(|step| cycle[(start.0 + len - step) % len]);
+
+            if directed || cycle[(start.0 + 1) % len] <= cycle[(start.0 + len - 1) % len] {
+                forward.for_each(|city| city.hash(&mut hasher));
+            } else {
+                backward.for_each(|city| city.hash(&mut hasher));
+            }
+        }
+
+        hasher.finish()
+    }
+
+    pub fn improve(&mut self, local_search: &LocalSearchSolver) {
+        let (fitness, gnome) = local_search.improve(&self.gnome);
+
+        self.gnome = gnome;
+        self.fitness = fitness;
     }
 
     pub fn fitness(gnome: &[usize], matrix: &[Vec<u32>]) -> u32 {
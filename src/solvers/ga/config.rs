@@ -0,0 +1,86 @@
+use std::sync::mpsc::Sender;
+
+use super::adaptive::RateSchedule;
+use super::replacement::Replacement;
+use crate::solvers::precedence::Precedence;
+use crate::solvers::route::Route;
+
+#[derive(Clone)]
+pub struct GAConfig {
+    pub population: usize,
+    pub generations: usize,
+    pub elitism: usize,
+    pub crossover_rate: f32,
+    pub mutation_rate: f32,
+    /// Fraction of children polished with local search before they join the population.
+    pub memetic_rate: f32,
+    /// Mutates or replaces children identical to a tour already in the population. Off by
+    /// default so the baseline GA behaves as it always has.
+    pub reject_duplicates: bool,
+    /// Edge entropy below which the worst individuals are replaced by random immigrants.
+    /// Zero disables immigration.
+    pub diversity_threshold: f64,
+    pub immigration_rate: f32,
+    /// Chooses among crossover and mutation operators by adaptive pursuit instead of
+    /// always using exchange crossover and swap mutation.
+    pub adaptive_operators: bool,
+    /// Each individual carries and evolves its own mutation rate; `schedule` is ignored.
+    pub self_adaptive_mutation: bool,
+    pub schedule: RateSchedule,
+    pub replacement: Replacement,
+    /// Children bred per generation as a multiple of the population, used by (μ+λ) and
+    /// (μ,λ) replacement.
+    pub offspring_ratio: f32,
+    /// Closed tour or open path the individuals encode.
+    pub route: Route,
+    /// Pairs of cities every individual visits in order; children are repaired to keep them.
+    pub precedence: Precedence,
+    /// Seed for a reproducible run; a random seed is drawn when unset.
+    pub seed: Option<u64>,
+    /// Generations between checkpoints written to `checkpoint_path`. Zero disables them.
+    pub checkpoint_interval: usize,
+    pub checkpoint_path: String,
+    /// Dataset file the run solves and whether it was read as directed, recorded in
+    /// checkpoints so a run is only ever resumed on the same instance.
+    pub data: String,
+    pub directed: bool,
+    /// Receives every new best tour of the run, for replaying how it evolved.
+    pub progress: Option<Sender<Improvement>>,
+}
+
+/// A tour that beat every earlier one of a run, with the generation that found it.
+#[derive(Clone)]
+pub struct Improvement {
+    pub generation: usize,
+    pub cost: u32,
+    pub tour: Vec<usize>,
+}
+
+impl Default for GAConfig {
+    fn default() -> Self {
+        Self {
+            population: 1000,
+            generations: 100000,
+            elitism: 3,
+            crossover_rate: 0.7,
+            mutation_rate: 0.3,
+            memetic_rate: 0.0,
+            reject_duplicates: false,
+            diversity_threshold: 0.0,
+            immigration_rate: 0.5,
+            adaptive_operators: false,
+            self_adaptive_mutation: false,
+            schedule: RateSchedule::Constant,
+            replacement: Replacement::Generational,
+            offspring_ratio: 1.0,
+            route: Route::Closed,
+            precedence: Precedence::default(),
+            seed: None,
+            checkpoint_interval: 0,
+            checkpoint_path: "ga.checkpoint".to_string(),
+            data: String::new(),
+            directed: false,
+            progress: None,
+        }
+    }
+}
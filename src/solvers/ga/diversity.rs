@@ -0,0 +1,149 @@
+use std::collections::{BTreeMap, HashSet};
+
+use rand::rngs::StdRng;
+
+use super::chromosome::Chromosome;
+use super::ga_trait::GeneticAlgorithm;
+
+const SAMPLE: usize = 32;
+const UNIQUE_ATTEMPTS: usize = 5;
+
+/// Undirected edges of a closed tour, smaller city first.
+fn edges(gnome: &[usize]) -> HashSet<(usize, usize)> {
+    gnome
+        .windows(2)
+        .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+        .collect()
+}
+
+/// Fraction of edges in `a` that do not appear in `b`.
+pub fn edge_distance(a: &[usize], b: &[usize]) -> f64 {
+    let (a, b) = (edges(a), edges(b));
+
+    if a.is_empty() {
+        return 0.0;
+    }
+
+    a.difference(&b).count() as f64 / a.len() as f64
+}
+
+/// Average pairwise edge distance over an evenly spaced sample of the population.
+pub fn average_edge_distance(population: &[Chromosome]) -> f64 {
+    let step = population.len().div_ceil(SAMPLE).max(1);
+    let sample: Vec<&Chromosome> = population.iter().step_by(step).collect();
+
+    let mut total = 0.0;
+    let mut pairs = 0;
+
+    for (i, a) in sample.iter().enumerate() {
+        for b in &sample[i + 1..] {
+            total += edge_distance(&a.gnome, &b.gnome);
+            pairs += 1;
+        }
+    }
+
+    if pairs == 0 {
+        0.0
+    } else {
+        total / pairs as f64
+    }
+}
+
+/// Shannon entropy of edge usage across the population, scaled so that 0 means every
+/// individual is the same tour and 1 means edges are spread as evenly as possible.
+pub fn edge_entropy(population: &[Chromosome]) -> f64 {
+    let mut counts: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+
+    population
+        .iter()
+        .flat_map(|individual| individual.gnome.windows(2))
+        .for_each(|pair| {
+            *counts
+                .entry((pair[0].min(pair[1]), pair[0].max(pair[1])))
+                .or_default() += 1
+        });
+
+    let total: usize = counts.values().sum();
+    let tour_edges = population.first().map_or(0, |first| first.gnome.len() - 1);
+
+    if total == 0 || tour_edges < 2 {
+        return 0.0;
+    }
+
+    let entropy: f64 = counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.ln()
+        })
+        .sum();
+
+    let cities = tour_edges as f64;
+    let floor = cities.ln();
+    let ceiling = (total as f64).min(cities * (cities - 1.0) / 2.0).ln();
+
+    if ceiling <= floor {
+        0.0
+    } else {
+        ((entropy - floor) / (ceiling - floor)).clamp(0.0, 1.0)
+    }
+}
+
+pub fn report(population: &[Chromosome]) {
+    println!(
+        "DIVERSITY: edge distance {:.3}, entropy {:.3}",
+        average_edge_distance(population),
+        edge_entropy(population)
+    );
+}
+
+/// Applies the diversity settings of `config` to a freshly bred population: duplicates of
+/// tours already present are mutated or replaced, and when edge entropy falls below the
+/// threshold the worst individuals are swapped for random immigrants. Elites are kept.
+pub fn maintain<G: GeneticAlgorithm + ?Sized>(
+    ga: &G,
+    population: &mut [Chromosome],
+    rng: &mut StdRng,
+) {
+    let config = ga.config();
+    let elitism = config.elitism.min(population.len());
+
+    if config.reject_duplicates {
+        let directed = !ga.symmetric();
+        let mut seen: HashSet<u64> = population[..elitism]
+            .iter()
+            .map(|individual| individual.identity(config.route, directed))
+            .collect();
+
+        for individual in population[elitism..].iter_mut() {
+            let mut attempts = 0;
+
+            // Tiny instances have fewer distinct tours than individuals, so give up eventually.
+            while seen.contains(&individual.identity(config.route, directed))
+                && attempts < UNIQUE_ATTEMPTS * 2
+            {
+                if attempts < UNIQUE_ATTEMPTS {
+                    ga.mutate(individual, rng);
+                    ga.repair(individual);
+                } else {
+                    *individual = ga.random_individual(rng);
+                }
+
+                attempts += 1;
+            }
+
+            seen.insert(individual.identity(config.route, directed));
+        }
+    }
+
+    if config.diversity_threshold > 0.0 && edge_entropy(population) < config.diversity_threshold {
+        population.sort();
+
+        let len = population.len();
+        let immigrants = ((len as f32 * config.immigration_rate) as usize).min(len - elitism);
+
+        population[len - immigrants..]
+            .iter_mut()
+            .for_each(|individual| *individual = ga.random_individual(rng));
+    }
+}
@@ -1,9 +1,43 @@
+use rand::rngs::StdRng;
+
 use super::chromosome::Chromosome;
+use super::config::GAConfig;
+use crate::solvers::local_search::LocalSearchSolver;
 
 pub trait GeneticAlgorithm {
     fn solve(&self) -> (u32, Vec<usize>);
-    fn random_gnome(&self) -> Vec<usize>;
-    fn crossover(&self, parent_1: &Chromosome, parent_2: &Chromosome) -> (Chromosome, Chromosome);
-    fn mutate(&self, individual: &mut Chromosome);
-    fn select(&self, population: &[Chromosome]) -> (usize, usize);
+    fn matrix(&self) -> &[Vec<u32>];
+    /// Whether the matrix reads the same both ways, worked out once when it is set.
+    fn symmetric(&self) -> bool;
+    fn config(&self) -> &GAConfig;
+    fn local_search(&self) -> &LocalSearchSolver;
+    fn random_gnome(&self, rng: &mut StdRng) -> Vec<usize>;
+    fn crossover(
+        &self,
+        parent_1: &Chromosome,
+        parent_2: &Chromosome,
+        rng: &mut StdRng,
+    ) -> (Chromosome, Chromosome);
+    fn mutate(&self, individual: &mut Chromosome, rng: &mut StdRng);
+    fn select(&self, population: &[Chromosome], rng: &mut StdRng) -> (usize, usize);
+
+    fn random_individual(&self, rng: &mut StdRng) -> Chromosome {
+        let mut individual = Chromosome::new(self.matrix(), self.random_gnome(rng));
+        individual.mutation_rate = self.config().mutation_rate;
+        self.repair(&mut individual);
+        individual
+    }
+
+    /// Reorders the movable cities of an individual so it keeps every precedence pair.
+    fn repair(&self, individual: &mut Chromosome) {
+        let config = self.config();
+
+        if config.precedence.is_empty() {
+            return;
+        }
+
+        let interior = config.route.interior(self.matrix().len());
+        config.precedence.repair(&mut individual.gnome, interior);
+        individual.fitness = Chromosome::fitness(&individual.gnome, self.matrix());
+    }
 }
@@ -0,0 +1,252 @@
+use std::fmt;
+use std::io::{self, ErrorKind};
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
+
+use crate::solvers::ga::checkpoint::{Checkpoint, Strategy};
+use crate::solvers::ga::chromosome::Chromosome;
+use crate::solvers::ga::config::GAConfig;
+use crate::solvers::ga::diversity;
+use crate::solvers::ga::rng;
+use crate::solvers::ga::sequential::SequentialGASolver;
+use crate::solvers::local_search::LocalSearchSolver;
+
+use super::ga_trait::GeneticAlgorithm;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum MigrationTopology {
+    Ring,
+    FullyConnected,
+    Random,
+}
+
+impl MigrationTopology {
+    pub const ALL: [MigrationTopology; 3] = [
+        MigrationTopology::Ring,
+        MigrationTopology::FullyConnected,
+        MigrationTopology::Random,
+    ];
+}
+
+impl fmt::Display for MigrationTopology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MigrationTopology::Ring => "Ring",
+            MigrationTopology::FullyConnected => "Fully connected",
+            MigrationTopology::Random => "Random",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Splits the population into islands that evolve independently on the Rayon pool and
+/// exchange their best individuals every `migration_interval` generations.
+pub struct IslandGASolver {
+    pub ga: SequentialGASolver,
+    pub islands: usize,
+    pub migration_interval: usize,
+    pub migration_size: usize,
+    pub topology: MigrationTopology,
+}
+
+impl IslandGASolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        Self {
+            ga: SequentialGASolver::new(matrix),
+            islands: 8,
+            migration_interval: 50,
+            migration_size: 2,
+            topology: MigrationTopology::Ring,
+        }
+    }
+
+    pub fn resume(&mut self, checkpoint: Checkpoint) -> io::Result<(u32, Vec<usize>)> {
+        let Strategy::Island {
+            migration_interval,
+            migration_size,
+            topology,
+        } = checkpoint.strategy
+        else {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "checkpoint was not written by the island GA",
+            ));
+        };
+
+        self.ga.config = checkpoint.resumed_config(&self.ga.config)?;
+        self.islands = checkpoint.populations.len();
+        self.migration_interval = migration_interval;
+        self.migration_size = migration_size;
+        self.topology = topology;
+
+        Ok(self.run(checkpoint))
+    }
+
+    fn run(&self, mut state: Checkpoint) -> (u32, Vec<usize>) {
+        let config = &self.ga.config;
+        let interval = self.migration_interval.max(1);
+        let seed = state.seed;
+
+        while state.generation < config.generations {
+            let previous = state.generation;
+            let epoch = interval.min(config.generations - previous);
+
+            state
+                .populations
+                .par_iter_mut()
+                .zip(state.controls.par_iter_mut())
+                .enumerate()
+                .for_each(|(island, (population, control))| {
+                    let mut rng = rng::stream(seed, previous + 1, island);
+
+                    for step in 0..epoch {
+                        let progress = (previous + step) as f64 / config.generations as f64;
+                        *population = self
+                            .ga
+                            .next_generation(population, control, progress, &mut rng);
+                    }
+                });
+
+            state.generation += epoch;
+
+            if state.generation < config.generations {
+                let mut rng = rng::stream(seed, previous + 1, state.populations.len());
+                self.migrate(&mut state.populations, &mut rng);
+            }
+
+            state.advance(config, previous);
+        }
+
+        diversity::report(&state.populations.concat());
+
+        state
+            .populations
+            .iter()
+            .zip(&state.controls)
+            .enumerate()
+            .for_each(|(island, (population, control))| {
+                if config.adaptive_operators || config.self_adaptive_mutation {
+                    println!("ISLAND {island}");
+                }
+
+                control.report(population);
+            });
+
+        state.result()
+    }
+
+    /// Replaces the worst individuals of every island with the best individuals of the
+    /// islands it is connected to. Elites are never replaced.
+    fn migrate(&self, populations: &mut [Vec<Chromosome>], rng: &mut StdRng) {
+        let count = populations.len();
+
+        let emigrants: Vec<Vec<Chromosome>> = populations
+            .iter()
+            .map(|population| population[..self.migration_size.min(population.len())].to_vec())
+            .collect();
+
+        populations
+            .iter_mut()
+            .enumerate()
+            .for_each(|(island, population)| {
+                let sources: Vec<usize> = match self.topology {
+                    _ if count < 2 => vec![],
+                    MigrationTopology::Ring => vec![(island + count - 1) % count],
+                    MigrationTopology::FullyConnected => {
+                        (0..count).filter(|&other| other != island).collect()
+                    }
+                    MigrationTopology::Random => {
+                        let other = rng.random_range(1..count);
+                        vec![(island + other) % count]
+                    }
+                };
+
+                let mut incoming: Vec<Chromosome> = sources
+                    .into_iter()
+                    .flat_map(|source| emigrants[source].iter().cloned())
+                    .collect();
+
+                incoming.sort();
+                incoming.truncate(
+                    self.migration_size
+                        .min(population.len().saturating_sub(self.ga.config.elitism)),
+                );
+
+                population.truncate(population.len() - incoming.len());
+                population.append(&mut incoming);
+                population.sort();
+            });
+    }
+}
+
+impl GeneticAlgorithm for IslandGASolver {
+    fn solve(&self) -> (u32, Vec<usize>) {
+        let config = &self.ga.config;
+        let islands = self.islands.max(1);
+        let island_size = (config.population / islands).max(2);
+        let seed = config.seed.unwrap_or_else(|| rand::rng().random());
+
+        let populations: Vec<Vec<Chromosome>> = (0..islands)
+            .into_par_iter()
+            .map(|island| {
+                let mut rng = rng::stream(seed, 0, island);
+                let mut population: Vec<Chromosome> = (0..island_size)
+                    .map(|_| self.random_individual(&mut rng))
+                    .collect();
+
+                population.sort();
+                population
+            })
+            .collect();
+
+        let strategy = Strategy::Island {
+            migration_interval: self.migration_interval,
+            migration_size: self.migration_size,
+            topology: self.topology,
+        };
+
+        self.run(Checkpoint::new(strategy, config, seed, populations))
+    }
+
+    fn matrix(&self) -> &[Vec<u32>] {
+        self.ga.matrix()
+    }
+
+    fn symmetric(&self) -> bool {
+        self.ga.symmetric()
+    }
+
+    fn config(&self) -> &GAConfig {
+        self.ga.config()
+    }
+
+    fn local_search(&self) -> &LocalSearchSolver {
+        self.ga.local_search()
+    }
+
+    fn random_gnome(&self, rng: &mut StdRng) -> Vec<usize> {
+        self.ga.random_gnome(rng)
+    }
+
+    fn select(&self, population: &[Chromosome], rng: &mut StdRng) -> (usize, usize) {
+        self.ga.select(population, rng)
+    }
+
+    fn crossover(
+        &self,
+        parent_1: &Chromosome,
+        parent_2: &Chromosome,
+        rng: &mut StdRng,
+    ) -> (Chromosome, Chromosome) {
+        self.ga.crossover(parent_1, parent_2, rng)
+    }
+
+    fn mutate(&self, individual: &mut Chromosome, rng: &mut StdRng) {
+        self.ga.mutate(individual, rng)
+    }
+}
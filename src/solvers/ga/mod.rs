@@ -1,4 +1,12 @@
+pub mod adaptive;
+pub mod checkpoint;
 mod chromosome;
+pub mod config;
+mod diversity;
 pub mod ga_trait;
+pub mod island;
+mod operators;
 pub mod parallel;
+pub mod replacement;
+mod rng;
 pub mod sequential;
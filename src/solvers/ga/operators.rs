@@ -0,0 +1,255 @@
+use std::fmt;
+use std::ops::Range;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use super::adaptive::{self, Credit, OperatorControl};
+use super::chromosome::Chromosome;
+use super::ga_trait::GeneticAlgorithm;
+use super::replacement::Family;
+use crate::solvers::route::Route;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum CrossoverOperator {
+    Exchange,
+    Order,
+    PartiallyMapped,
+}
+
+impl CrossoverOperator {
+    pub const ALL: [CrossoverOperator; 3] = [
+        CrossoverOperator::Exchange,
+        CrossoverOperator::Order,
+        CrossoverOperator::PartiallyMapped,
+    ];
+}
+
+impl fmt::Display for CrossoverOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CrossoverOperator::Exchange => "Exchange",
+            CrossoverOperator::Order => "Order (OX)",
+            CrossoverOperator::PartiallyMapped => "Partially mapped (PMX)",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum MutationOperator {
+    Swap,
+    Inversion,
+    Insertion,
+}
+
+impl MutationOperator {
+    pub const ALL: [MutationOperator; 3] = [
+        MutationOperator::Swap,
+        MutationOperator::Inversion,
+        MutationOperator::Insertion,
+    ];
+}
+
+impl fmt::Display for MutationOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MutationOperator::Swap => "Swap",
+            MutationOperator::Inversion => "Inversion",
+            MutationOperator::Insertion => "Insertion",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Selects two parents and breeds two children with the operators picked by `control`,
+/// noting the improvement each operator made. `progress` is the fraction of the run
+/// already done and drives the mutation rate schedule.
+pub fn breed<G: GeneticAlgorithm + ?Sized>(
+    ga: &G,
+    control: &OperatorControl,
+    population: &[Chromosome],
+    progress: f64,
+    rng: &mut StdRng,
+) -> Family {
+    let config = ga.config();
+    let mut credits = vec![];
+    let (p_1, p_2) = ga.select(population, rng);
+    let (parent_1, parent_2) = (&population[p_1], &population[p_2]);
+
+    let (mut child_1, mut child_2) = if rng.random::<f32>() < config.crossover_rate {
+        let operator = control.crossover(rng);
+        let children = crossover(ga, operator, parent_1, parent_2, rng);
+
+        credits.push(Credit::crossover(
+            operator,
+            parent_1.fitness.min(parent_2.fitness),
+            children.0.fitness.min(children.1.fitness),
+        ));
+
+        children
+    } else {
+        (parent_1.clone(), parent_2.clone())
+    };
+
+    let inherited = (parent_1.mutation_rate + parent_2.mutation_rate) / 2.0;
+
+    for child in [&mut child_1, &mut child_2] {
+        let rate = if config.self_adaptive_mutation {
+            child.mutation_rate = adaptive::perturb(inherited, rng);
+            child.mutation_rate
+        } else {
+            child.mutation_rate = config.mutation_rate;
+            config.mutation_rate * config.schedule.factor(progress)
+        };
+
+        if rng.random::<f32>() < rate {
+            let operator = control.mutation(rng);
+            let before = child.fitness;
+
+            mutate(ga, operator, child, rng);
+            credits.push(Credit::mutation(operator, before, child.fitness));
+        }
+
+        // Local search polishes closed tours only.
+        if rng.random::<f32>() < config.memetic_rate && config.route == Route::Closed {
+            child.improve(ga.local_search());
+            ga.repair(child);
+        }
+    }
+
+    Family {
+        parents: (p_1, p_2),
+        children: (child_1, child_2),
+        credits,
+    }
+}
+
+fn crossover<G: GeneticAlgorithm + ?Sized>(
+    ga: &G,
+    operator: CrossoverOperator,
+    parent_1: &Chromosome,
+    parent_2: &Chromosome,
+    rng: &mut StdRng,
+) -> (Chromosome, Chromosome) {
+    let interior = ga.config().route.interior(ga.matrix().len());
+
+    let (mut child_1, mut child_2) = match operator {
+        CrossoverOperator::Exchange => ga.crossover(parent_1, parent_2, rng),
+        _ => {
+            let (cut_1, cut_2) = cuts(&interior, rng);
+
+            let child = |donor: &Chromosome, other: &Chromosome| {
+                let (donor, other) = (&donor.gnome, &other.gnome);
+                let gnome = match operator {
+                    CrossoverOperator::PartiallyMapped => {
+                        pmx(donor, other, &interior, cut_1, cut_2)
+                    }
+                    _ => ox(donor, other, &interior, cut_1, cut_2),
+                };
+
+                Chromosome::new(ga.matrix(), gnome)
+            };
+
+            (child(parent_1, parent_2), child(parent_2, parent_1))
+        }
+    };
+
+    ga.repair(&mut child_1);
+    ga.repair(&mut child_2);
+
+    (child_1, child_2)
+}
+
+fn mutate<G: GeneticAlgorithm + ?Sized>(
+    ga: &G,
+    operator: MutationOperator,
+    individual: &mut Chromosome,
+    rng: &mut StdRng,
+) {
+    let (i, j) = match operator {
+        MutationOperator::Swap => {
+            ga.mutate(individual, rng);
+            return ga.repair(individual);
+        }
+        _ => cuts(&ga.config().route.interior(ga.matrix().len()), rng),
+    };
+
+    match operator {
+        MutationOperator::Inversion => individual.gnome[i..=j].reverse(),
+        _ => {
+            let city = individual.gnome.remove(i);
+            individual.gnome.insert(j, city);
+        }
+    }
+
+    individual.fitness = Chromosome::fitness(&individual.gnome, ga.matrix());
+    ga.repair(individual);
+}
+
+/// Two positions `i <= j` within `interior`, so the fixed ends of the route are never
+/// moved.
+fn cuts(interior: &Range<usize>, rng: &mut StdRng) -> (usize, usize) {
+    if interior.len() < 2 {
+        return (interior.start, interior.start);
+    }
+
+    let (a, b) = (
+        rng.random_range(interior.clone()),
+        rng.random_range(interior.clone()),
+    );
+
+    (a.min(b), a.max(b))
+}
+
+/// Order crossover: keeps `donor[i..=j]` in place and fills the remaining `interior`
+/// positions with the other cities in the order they appear in `other`.
+fn ox(donor: &[usize], other: &[usize], interior: &Range<usize>, i: usize, j: usize) -> Vec<usize> {
+    let mut taken = vec![false; donor.len()];
+    donor[i..=j].iter().for_each(|&city| taken[city] = true);
+
+    let mut rest = other[interior.clone()].iter().filter(|&&city| !taken[city]);
+    let mut child = donor.to_vec();
+
+    interior
+        .clone()
+        .filter(|&idx| idx < i || idx > j)
+        .for_each(|idx| child[idx] = *rest.next().unwrap());
+
+    child
+}
+
+/// Partially mapped crossover: keeps `donor[i..=j]` in place and fills the remaining
+/// `interior` positions from `other`, following the segment's mapping to resolve conflicts.
+fn pmx(
+    donor: &[usize],
+    other: &[usize],
+    interior: &Range<usize>,
+    i: usize,
+    j: usize,
+) -> Vec<usize> {
+    let mut position = vec![usize::MAX; donor.len()];
+    donor[i..=j]
+        .iter()
+        .enumerate()
+        .for_each(|(offset, &city)| position[city] = i + offset);
+
+    let mut child = donor.to_vec();
+
+    interior
+        .clone()
+        .filter(|&idx| idx < i || idx > j)
+        .for_each(|idx| {
+            let mut city = other[idx];
+
+            while position[city] != usize::MAX {
+                city = other[position[city]];
+            }
+
+            child[idx] = city;
+        });
+
+    child
+}
@@ -1,92 +1,140 @@
+use std::io::{self, ErrorKind};
+
 use rand::{
     Rng,
     distr::{Distribution, weighted::WeightedIndex},
+    rngs::StdRng,
     seq::SliceRandom,
 };
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+use crate::solvers::ga::checkpoint::{Checkpoint, Strategy};
 use crate::solvers::ga::chromosome::Chromosome;
+use crate::solvers::ga::config::GAConfig;
+use crate::solvers::ga::diversity;
+use crate::solvers::ga::operators;
+use crate::solvers::ga::replacement::{self, Family};
+use crate::solvers::ga::rng;
+use crate::solvers::local_search::LocalSearchSolver;
+use crate::solvers::tour;
 
 use super::ga_trait::GeneticAlgorithm;
 
 pub struct ParallelGASolver {
     matrix: Vec<Vec<u32>>,
+    symmetric: bool,
+    local_search: LocalSearchSolver,
+    pub config: GAConfig,
 }
 
 impl ParallelGASolver {
     pub fn new(matrix: Vec<Vec<u32>>) -> Self {
-        Self { matrix }
+        Self {
+            local_search: LocalSearchSolver::new(matrix.clone()),
+            symmetric: tour::is_symmetric(&matrix),
+            matrix,
+            config: GAConfig::default(),
+        }
     }
 }
 
-impl GeneticAlgorithm for ParallelGASolver {
-    fn solve(&self) -> (u32, Vec<usize>) {
-        let gen_threshold = 100000;
-        let elitism = 3;
-
-        let mut population: Vec<Chromosome> = (0..1000)
-            .into_par_iter()
-            .map(|_| Chromosome::new(&self.matrix, self.random_gnome()))
-            .collect();
+impl ParallelGASolver {
+    pub fn resume(&mut self, checkpoint: Checkpoint) -> io::Result<(u32, Vec<usize>)> {
+        if checkpoint.strategy != Strategy::Parallel {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "checkpoint was not written by the parallel GA",
+            ));
+        }
 
-        population.sort();
+        self.config = checkpoint.resumed_config(&self.config)?;
 
-        let pop_len = population.len();
+        Ok(self.run(checkpoint))
+    }
 
-        for _ in 0..gen_threshold {
-            let new_children: Vec<Chromosome> = (elitism..pop_len)
-                .into_par_iter()
-                .map(|_| {
-                    let (p_1, p_2) = self.select(&population);
+    fn run(&self, mut state: Checkpoint) -> (u32, Vec<usize>) {
+        let generations = self.config.generations;
+        let seed = state.seed;
 
-                    let (mut child_1, mut child_2) = if rand::rng().random::<f32>() < 0.7 {
-                        self.crossover(&population[p_1], &population[p_2])
-                    } else {
-                        (population[p_1].clone(), population[p_2].clone())
-                    };
+        while state.generation < generations {
+            let previous = state.generation;
+            let progress = previous as f64 / generations as f64;
+            let population = &state.populations[0];
+            let control = &state.controls[0];
 
-                    if rand::rng().random::<f32>() < 0.3 {
-                        self.mutate(&mut child_1);
-                    }
+            let families: Vec<Family> =
+                (0..replacement::parallel_families(&self.config, population.len()))
+                    .into_par_iter()
+                    .map(|idx| {
+                        let mut rng = rng::stream(seed, previous + 1, idx + 1);
+                        operators::breed(self, control, population, progress, &mut rng)
+                    })
+                    .collect();
 
-                    if rand::rng().random::<f32>() < 0.3 {
-                        self.mutate(&mut child_2);
-                    }
+            state.controls[0].record(&families);
 
-                    if child_1.fitness < child_2.fitness {
-                        child_1
-                    } else {
-                        child_2
-                    }
-                })
-                .collect();
+            let mut population = replacement::replace_parallel(&self.config, population, families);
 
-            population.splice(elitism.., new_children);
+            diversity::maintain(
+                self,
+                &mut population,
+                &mut rng::stream(seed, previous + 1, 0),
+            );
 
             population.sort();
+
+            state.populations[0] = population;
+            state.generation += 1;
+            state.advance(&self.config, previous);
         }
 
-        let minimum = population.iter().min_by(|x, y| x.fitness.cmp(&y.fitness));
+        diversity::report(&state.populations[0]);
+        state.controls[0].report(&state.populations[0]);
 
-        match minimum {
-            Some(val) => (val.fitness, val.gnome.clone()),
-            None => (0, vec![]),
-        }
+        state.result()
+    }
+}
+
+impl GeneticAlgorithm for ParallelGASolver {
+    fn solve(&self) -> (u32, Vec<usize>) {
+        let seed = self.config.seed.unwrap_or_else(|| rand::rng().random());
+
+        let mut population: Vec<Chromosome> = (0..self.config.population)
+            .into_par_iter()
+            .map(|idx| self.random_individual(&mut rng::stream(seed, 0, idx)))
+            .collect();
+
+        population.sort();
+
+        self.run(Checkpoint::new(
+            Strategy::Parallel,
+            &self.config,
+            seed,
+            vec![population],
+        ))
     }
 
-    fn random_gnome(&self) -> Vec<usize> {
-        let len = self.matrix.len();
-        let mut path: Vec<usize> = vec![0];
+    fn matrix(&self) -> &[Vec<u32>] {
+        &self.matrix
+    }
+
+    fn symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    fn config(&self) -> &GAConfig {
+        &self.config
+    }
 
-        let mut cities: Vec<usize> = (1..len).collect();
-        cities.shuffle(&mut rand::rng());
+    fn local_search(&self) -> &LocalSearchSolver {
+        &self.local_search
+    }
 
-        path.append(&mut cities);
-        path.push(0);
-        path
+    fn random_gnome(&self, rng: &mut StdRng) -> Vec<usize> {
+        self.config.route.random(self.matrix.len(), rng)
     }
 
-    fn select(&self, population: &[Chromosome]) -> (usize, usize) {
+    fn select(&self, population: &[Chromosome], rng: &mut StdRng) -> (usize, usize) {
         let weights: Vec<u32> = population.iter().map(|ind| ind.fitness).collect();
         let inverted: Vec<f64> = weights.iter().map(|&w| 1.0 / (w as f64 + 1.0)).collect();
         let sum: f64 = inverted.iter().sum();
@@ -95,16 +143,20 @@ impl GeneticAlgorithm for ParallelGASolver {
             .map(|&inv| ((inv / sum) * 1000.0).round() as u64)
             .collect();
         let dist = WeightedIndex::new(&normalized).unwrap();
-        let mut rng = rand::rng();
 
-        (dist.sample(&mut rng), dist.sample(&mut rng))
+        (dist.sample(rng), dist.sample(rng))
     }
 
-    fn crossover(&self, parent_1: &Chromosome, parent_2: &Chromosome) -> (Chromosome, Chromosome) {
-        let mut cities: Vec<usize> = (1..self.matrix.len()).collect();
-        cities.shuffle(&mut rand::rng());
+    fn crossover(
+        &self,
+        parent_1: &Chromosome,
+        parent_2: &Chromosome,
+        rng: &mut StdRng,
+    ) -> (Chromosome, Chromosome) {
+        let mut cities = self.config.route.movable(self.matrix.len());
+        cities.shuffle(rng);
 
-        let chosen = cities[..3].to_vec();
+        let chosen = cities[..cities.len().min(3)].to_vec();
 
         let order_1 = parent_1
             .gnome
@@ -151,17 +203,21 @@ impl GeneticAlgorithm for ParallelGASolver {
         )
     }
 
-    fn mutate(&self, individual: &mut Chromosome) {
-        let len = self.matrix.len();
+    fn mutate(&self, individual: &mut Chromosome, rng: &mut StdRng) {
+        let interior = self.config.route.interior(self.matrix.len());
+
+        if interior.len() < 2 {
+            return;
+        }
 
-        let (r, r1): (usize, usize) = (|&len| loop {
-            let rr = rand::rng().random_range(1..len);
-            let rr1 = rand::rng().random_range(1..len);
+        let (r, r1): (usize, usize) = loop {
+            let rr = rng.random_range(interior.clone());
+            let rr1 = rng.random_range(interior.clone());
 
             if rr != rr1 {
-                return (rr, rr1);
+                break (rr, rr1);
             }
-        })(&len);
+        };
 
         let mut gnome = individual.gnome.clone();
 
@@ -0,0 +1,196 @@
+use std::fmt;
+
+use super::adaptive::Credit;
+use super::chromosome::Chromosome;
+use super::config::GAConfig;
+use super::diversity;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Replacement {
+    Generational,
+    SteadyState,
+    MuPlusLambda,
+    MuCommaLambda,
+    Crowding,
+}
+
+impl Replacement {
+    pub const ALL: [Replacement; 5] = [
+        Replacement::Generational,
+        Replacement::SteadyState,
+        Replacement::MuPlusLambda,
+        Replacement::MuCommaLambda,
+        Replacement::Crowding,
+    ];
+}
+
+impl fmt::Display for Replacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Replacement::Generational => "Generational",
+            Replacement::SteadyState => "Steady-state",
+            Replacement::MuPlusLambda => "(μ+λ)",
+            Replacement::MuCommaLambda => "(μ,λ)",
+            Replacement::Crowding => "Crowding",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Two children together with the indices of the parents they were bred from and the
+/// credit earned by the operators that bred them.
+pub struct Family {
+    pub parents: (usize, usize),
+    pub children: (Chromosome, Chromosome),
+    pub credits: Vec<Credit>,
+}
+
+/// How many families one generation breeds from a population of `len`.
+pub fn families(config: &GAConfig, len: usize) -> usize {
+    let offspring = (len as f32 * config.offspring_ratio).round() as usize;
+
+    match config.replacement {
+        Replacement::Generational => len.saturating_sub(config.elitism).div_ceil(2),
+        Replacement::SteadyState => 1,
+        Replacement::MuPlusLambda | Replacement::MuCommaLambda => offspring.div_ceil(2).max(1),
+        Replacement::Crowding => len.div_ceil(2),
+    }
+}
+
+/// How many families the parallel GA breeds from a population of `len`. Its generational
+/// replacement breeds one family per place outside the elites, keeping the fitter child.
+pub fn parallel_families(config: &GAConfig, len: usize) -> usize {
+    match config.replacement {
+        Replacement::Generational => len.saturating_sub(config.elitism),
+        _ => families(config, len),
+    }
+}
+
+/// `replace` for the parallel GA, whose generational replacement keeps the elites and
+/// only the fitter child of every family.
+pub fn replace_parallel(
+    config: &GAConfig,
+    population: &[Chromosome],
+    families: Vec<Family>,
+) -> Vec<Chromosome> {
+    if config.replacement != Replacement::Generational {
+        return replace(config, population, families);
+    }
+
+    let elitism = config.elitism.min(population.len());
+    let mut next: Vec<Chromosome> = population[..elitism]
+        .iter()
+        .cloned()
+        .chain(families.into_iter().map(|family| {
+            let (child_1, child_2) = family.children;
+
+            if child_1.fitness < child_2.fitness {
+                child_1
+            } else {
+                child_2
+            }
+        }))
+        .take(population.len())
+        .collect();
+
+    next.sort();
+    next
+}
+
+/// Builds the next sorted population from the sorted `population` and the families bred
+/// from it. The population keeps its size under every strategy.
+pub fn replace(
+    config: &GAConfig,
+    population: &[Chromosome],
+    families: Vec<Family>,
+) -> Vec<Chromosome> {
+    let len = population.len();
+
+    let mut next = match config.replacement {
+        Replacement::Generational => {
+            let elitism = config.elitism.min(len);
+
+            population[..elitism]
+                .iter()
+                .cloned()
+                .chain(children(families))
+                .take(len)
+                .collect()
+        }
+        Replacement::SteadyState => {
+            let mut next = population.to_vec();
+
+            children(families).for_each(|child| {
+                if next
+                    .last()
+                    .is_some_and(|worst| child.fitness < worst.fitness)
+                {
+                    next.pop();
+                    let idx = next.partition_point(|other| other.fitness <= child.fitness);
+                    next.insert(idx, child);
+                }
+            });
+
+            next
+        }
+        Replacement::MuPlusLambda => {
+            let mut next: Vec<Chromosome> = population
+                .iter()
+                .cloned()
+                .chain(children(families))
+                .collect();
+
+            next.sort();
+            next.truncate(len);
+            next
+        }
+        Replacement::MuCommaLambda => {
+            let mut next: Vec<Chromosome> = children(families).collect();
+
+            next.sort();
+            next.truncate(len);
+
+            // Too few children to refill the population: top it up with the best parents.
+            let missing = len - next.len();
+            next.extend(population[..missing].iter().cloned());
+            next
+        }
+        Replacement::Crowding => {
+            let mut next = population.to_vec();
+
+            families.into_iter().for_each(|family| {
+                let (p_1, p_2) = family.parents;
+                let (child_1, child_2) = family.children;
+
+                let straight = diversity::edge_distance(&child_1.gnome, &population[p_1].gnome)
+                    + diversity::edge_distance(&child_2.gnome, &population[p_2].gnome);
+                let crossed = diversity::edge_distance(&child_1.gnome, &population[p_2].gnome)
+                    + diversity::edge_distance(&child_2.gnome, &population[p_1].gnome);
+
+                let pairs = if straight <= crossed {
+                    [(p_1, child_1), (p_2, child_2)]
+                } else {
+                    [(p_2, child_1), (p_1, child_2)]
+                };
+
+                pairs.into_iter().for_each(|(parent, child)| {
+                    if child.fitness < next[parent].fitness {
+                        next[parent] = child;
+                    }
+                });
+            });
+
+            next
+        }
+    };
+
+    next.sort();
+    next
+}
+
+fn children(families: Vec<Family>) -> impl Iterator<Item = Chromosome> {
+    families
+        .into_iter()
+        .flat_map(|family| [family.children.0, family.children.1])
+}
@@ -0,0 +1,16 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Random number generator for one stream of one generation. Every generation draws from
+/// fresh streams derived from the run seed, so a run can be replayed from any generation
+/// and parallel tasks never depend on scheduling order.
+pub fn stream(seed: u64, generation: usize, stream: usize) -> StdRng {
+    let mut z = seed
+        ^ (generation as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (stream as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    StdRng::seed_from_u64(z ^ (z >> 31))
+}
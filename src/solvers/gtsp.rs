@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::local_search::LocalSearchSolver;
+use super::tour;
+
+/// Most groups solved exactly; the DP table holds `2^(groups - 1) · cities` entries.
+const DP_MAX_GROUPS: usize = 16;
+const ELITISM: usize = 2;
+const TOURNAMENT: usize = 3;
+const CROSSOVER_RATE: f64 = 0.9;
+const MUTATION_RATE: f64 = 0.3;
+
+/// A solution of the GA: the order in which groups are visited and the city chosen in
+/// every group, indexed by group.
+#[derive(Clone)]
+struct Individual {
+    order: Vec<usize>,
+    choice: Vec<usize>,
+    cost: u32,
+}
+
+/// Generalised TSP: the shortest closed tour that visits exactly one city of every group.
+pub struct GeneralisedSolver {
+    matrix: Vec<Vec<u32>>,
+    members: Vec<Vec<usize>>,
+    group_of: Vec<usize>,
+    local_search: LocalSearchSolver,
+    pub population: usize,
+    pub generations: usize,
+}
+
+impl GeneralisedSolver {
+    /// `labels[city]` is the group the city belongs to, such as its country.
+    pub fn new(matrix: Vec<Vec<u32>>, labels: &[String]) -> Self {
+        let mut groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        labels
+            .iter()
+            .enumerate()
+            .for_each(|(city, label)| groups.entry(label).or_default().push(city));
+
+        let members: Vec<Vec<usize>> = groups.into_values().collect();
+        let mut group_of = vec![0; labels.len()];
+        members.iter().enumerate().for_each(|(group, cities)| {
+            cities.iter().for_each(|&city| group_of[city] = group);
+        });
+
+        Self {
+            local_search: LocalSearchSolver::new(matrix.clone()),
+            matrix,
+            members,
+            group_of,
+            population: 100,
+            generations: 300,
+        }
+    }
+}
+
+impl GeneralisedSolver {
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        if self.members.is_empty() {
+            return (0, vec![]);
+        }
+
+        println!(
+            "GTSP: {} groups, solved {}",
+            self.members.len(),
+            if self.members.len() <= DP_MAX_GROUPS {
+                "exactly"
+            } else {
+                "with the GA"
+            }
+        );
+
+        if self.members.len() <= DP_MAX_GROUPS {
+            self.exact()
+        } else {
+            self.genetic()
+        }
+    }
+
+    /// Held-Karp over groups instead of cities, run once for every city of the smallest
+    /// group as the fixed start.
+    fn exact(&self) -> (u32, Vec<usize>) {
+        let n = self.matrix.len();
+        let first = (0..self.members.len())
+            .min_by_key(|&group| self.members[group].len())
+            .unwrap();
+
+        // Bit `b` of a mask stands for group `others[b]`.
+        let others: Vec<usize> = (0..self.members.len())
+            .filter(|&group| group != first)
+            .collect();
+        let mut bit = vec![usize::MAX; self.members.len()];
+        others
+            .iter()
+            .enumerate()
+            .for_each(|(b, &group)| bit[group] = b);
+
+        let size = 1 << others.len();
+        let full = size - 1;
+        let mut best = (u32::MAX, vec![]);
+
+        for &start in &self.members[first] {
+            if others.is_empty() {
+                return (0, vec![start, start]);
+            }
+
+            let mut dp = vec![vec![u32::MAX; n]; size];
+            let mut parent = vec![vec![start; n]; size];
+
+            others.iter().enumerate().for_each(|(b, &group)| {
+                self.members[group]
+                    .iter()
+                    .for_each(|&city| dp[1 << b][city] = self.matrix[start][city]);
+            });
+
+            for mask in 1..size {
+                for (b, &group) in others.iter().enumerate() {
+                    if mask & (1 << b) == 0 {
+                        continue;
+                    }
+
+                    for &u in &self.members[group] {
+                        let reached = dp[mask][u];
+
+                        if reached == u32::MAX {
+                            continue;
+                        }
+
+                        for (next, &other) in others.iter().enumerate() {
+                            if mask & (1 << next) != 0 {
+                                continue;
+                            }
+
+                            let next_mask = mask | (1 << next);
+
+                            for &v in &self.members[other] {
+                                let cost = reached.saturating_add(self.matrix[u][v]);
+
+                                if cost < dp[next_mask][v] {
+                                    dp[next_mask][v] = cost;
+                                    parent[next_mask][v] = u;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            let closing = others
+                .iter()
+                .flat_map(|&group| &self.members[group])
+                .map(|&u| (dp[full][u].saturating_add(self.matrix[u][start]), u))
+                .min();
+
+            let Some((cost, last)) = closing else {
+                continue;
+            };
+
+            if cost >= best.0 {
+                continue;
+            }
+
+            let mut path = vec![start];
+            let (mut mask, mut city) = (full, last);
+
+            while city != start {
+                path.push(city);
+                let prev = parent[mask][city];
+                mask ^= 1 << bit[self.group_of[city]];
+                city = prev;
+            }
+
+            path.push(start);
+            path.reverse();
+            best = (cost, path);
+        }
+
+        best
+    }
+
+    /// Memetic GA: order crossover on the group order, uniform crossover on the chosen
+    /// cities, and every child polished by local search on its order followed by the
+    /// optimal choice of cities for that order.
+    fn genetic(&self) -> (u32, Vec<usize>) {
+        let mut rng = rand::rng();
+        let groups = self.members.len();
+
+        let mut population: Vec<Individual> = (0..self.population.max(ELITISM + 1))
+            .map(|_| {
+                let mut order: Vec<usize> = (0..groups).collect();
+                order.shuffle(&mut rng);
+
+                let choice = self
+                    .members
+                    .iter()
+                    .map(|cities| cities[rng.random_range(0..cities.len())])
+                    .collect();
+
+                self.polish(Individual {
+                    order,
+                    choice,
+                    cost: 0,
+                })
+            })
+            .collect();
+
+        population.sort_by_key(|individual| individual.cost);
+
+        for generation in 0..self.generations {
+            let mut next: Vec<Individual> = population[..ELITISM].to_vec();
+
+            while next.len() < population.len() {
+                let parent_1 = tournament(&population, &mut rng);
+                let parent_2 = tournament(&population, &mut rng);
+
+                let mut child = if rng.random::<f64>() < CROSSOVER_RATE {
+                    Individual {
+                        order: tour::order_crossover(&parent_1.order, &parent_2.order, &mut rng),
+                        choice: parent_1
+                            .choice
+                            .iter()
+                            .zip(&parent_2.choice)
+                            .map(|(&a, &b)| if rng.random::<bool>() { a } else { b })
+                            .collect(),
+                        cost: 0,
+                    }
+                } else {
+                    parent_1.clone()
+                };
+
+                if rng.random::<f64>() < MUTATION_RATE && groups > 2 {
+                    let (i, j) = (rng.random_range(0..groups), rng.random_range(0..groups));
+                    child.order[i.min(j)..=i.max(j)].reverse();
+                }
+
+                if rng.random::<f64>() < MUTATION_RATE {
+                    let group = rng.random_range(0..groups);
+                    let cities = &self.members[group];
+                    child.choice[group] = cities[rng.random_range(0..cities.len())];
+                }
+
+                next.push(self.polish(child));
+            }
+
+            next.sort_by_key(|individual| individual.cost);
+            population = next;
+
+            if generation % 50 == 0 {
+                println!("GENERATION {generation}: {}", population[0].cost);
+            }
+        }
+
+        let best = &population[0];
+        (best.cost, self.decode(best))
+    }
+
+    fn decode(&self, individual: &Individual) -> Vec<usize> {
+        let mut path: Vec<usize> = individual
+            .order
+            .iter()
+            .map(|&group| individual.choice[group])
+            .collect();
+
+        path.push(path[0]);
+        path
+    }
+
+    fn polish(&self, mut individual: Individual) -> Individual {
+        let (_, path) = self.local_search.improve(&self.decode(&individual));
+
+        individual.order = path[..path.len() - 1]
+            .iter()
+            .map(|&city| self.group_of[city])
+            .collect();
+
+        self.choose_cities(&mut individual);
+        individual
+    }
+
+    /// Picks the best city of every group for a fixed group order: a shortest path through
+    /// the groups in order, tried from every city of the first group.
+    fn choose_cities(&self, individual: &mut Individual) {
+        let order = &individual.order;
+        let mut best = (u32::MAX, vec![]);
+
+        for &start in &self.members[order[0]] {
+            // Cheapest way to reach each city of the current group, and how it was reached.
+            let mut layer: Vec<(usize, u32)> = vec![(start, 0)];
+            let mut parents: Vec<Vec<usize>> = vec![];
+
+            for &group in &order[1..] {
+                let (next, from): (Vec<(usize, u32)>, Vec<usize>) = self.members[group]
+                    .iter()
+                    .map(|&city| {
+                        let (cost, parent) = layer
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, &(prev, cost))| (cost + self.matrix[prev][city], idx))
+                            .min()
+                            .unwrap();
+
+                        ((city, cost), parent)
+                    })
+                    .unzip();
+
+                layer = next;
+                parents.push(from);
+            }
+
+            let (cost, mut idx) = layer
+                .iter()
+                .enumerate()
+                .map(|(idx, &(city, cost))| (cost + self.matrix[city][start], idx))
+                .min()
+                .unwrap();
+
+            if cost >= best.0 {
+                continue;
+            }
+
+            let mut chosen = vec![layer[idx].0];
+            for (depth, from) in parents.iter().enumerate().rev() {
+                idx = from[idx];
+                chosen.push(if depth == 0 {
+                    start
+                } else {
+                    self.members[order[depth]][idx]
+                });
+            }
+
+            chosen.reverse();
+            best = (cost, chosen);
+        }
+
+        best.1
+            .iter()
+            .zip(order)
+            .for_each(|(&city, &group)| individual.choice[group] = city);
+        individual.cost = best.0;
+    }
+}
+
+fn tournament<'a>(population: &'a [Individual], rng: &mut impl Rng) -> &'a Individual {
+    (0..TOURNAMENT)
+        .map(|_| &population[rng.random_range(0..population.len())])
+        .min_by_key(|individual| individual.cost)
+        .unwrap()
+}
@@ -0,0 +1,426 @@
+use std::cmp::Reverse;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::{Rng, seq::SliceRandom};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::local_search;
+use super::tour::{self, Ring};
+
+const CANDIDATES: usize = 5;
+const MAX_DEPTH: usize = 50;
+const BREADTH: [usize; 4] = [5, 5, 3, 2];
+const TRIALS: usize = 8;
+/// Trials that have to reach the same best cost before the remaining ones are skipped.
+const AGREEMENT: usize = 3;
+const KICKS_PER_CITY: usize = 2;
+/// Kicks on the merged graph, which is sparse enough to afford many more.
+const MERGED_KICKS_PER_CITY: usize = 10;
+const KICK_SEGMENT: usize = 30;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum CandidateSet {
+    AlphaNearness,
+    NearestNeighbours,
+}
+
+pub struct LinKernighanSolver {
+    matrix: Vec<Vec<u32>>,
+    symmetric: bool,
+    pub candidate_set: CandidateSet,
+}
+
+impl LinKernighanSolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        Self {
+            symmetric: tour::is_symmetric(&matrix),
+            matrix,
+            candidate_set: CandidateSet::AlphaNearness,
+        }
+    }
+}
+
+impl LinKernighanSolver {
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        self.solve_until(&AtomicBool::new(false), &|_, _| {})
+    }
+
+    /// Lin-Kernighan that returns the best tour it has as soon as `stop` is set.
+    /// `improved` is told about every new best tour of a trial or of the merged search as
+    /// soon as it is found, from whichever thread found it.
+    pub fn solve_until(
+        &self,
+        stop: &AtomicBool,
+        improved: &(dyn Fn(u32, &[usize]) + Sync),
+    ) -> (u32, Vec<usize>) {
+        let len = self.matrix.len();
+
+        if len < 4 {
+            let path = tour::nearest_neighbour(&self.matrix, 0);
+            return (tour::cost(&self.matrix, &path), path);
+        }
+
+        // Alpha-nearness comes from 1-trees, which ignore direction.
+        let candidates = match self.candidate_set {
+            CandidateSet::AlphaNearness if self.symmetric => self.alpha_candidates(),
+            _ => tour::candidate_lists(&self.matrix, CANDIDATES),
+        };
+
+        // Trials that have not started yet are skipped once enough of the finished ones
+        // agree on the best cost, which is then almost always the optimum.
+        let agreed = AtomicBool::new(false);
+        let best = Mutex::new((u32::MAX, 0));
+
+        let trials: Vec<(u32, Vec<usize>)> = (0..TRIALS)
+            .into_par_iter()
+            .filter_map(|_| {
+                if agreed.load(Ordering::Relaxed) {
+                    return None;
+                }
+
+                let trial = self.trial(&candidates, stop, improved);
+                let mut best = best.lock().unwrap();
+
+                if trial.0 < best.0 {
+                    *best = (trial.0, 1);
+                } else if trial.0 == best.0 {
+                    best.1 += 1;
+                }
+
+                if best.1 >= AGREEMENT {
+                    agreed.store(true, Ordering::Relaxed);
+                }
+
+                Some(trial)
+            })
+            .collect();
+
+        // Tour merging: the edges of all trial tours form a sparse candidate graph which is
+        // searched once more, kicking the best trial.
+        let mut merged: Vec<HashSet<usize>> = vec![HashSet::new(); len];
+        trials.iter().for_each(|(_, path)| {
+            path.windows(2).for_each(|pair| {
+                merged[pair[0]].insert(pair[1]);
+                merged[pair[1]].insert(pair[0]);
+            })
+        });
+
+        let merged: Vec<Vec<usize>> = merged
+            .into_iter()
+            .enumerate()
+            .map(|(city, set)| {
+                let mut neighbours: Vec<usize> = set.into_iter().collect();
+                neighbours.sort_by_key(|&other| self.matrix[city][other]);
+                neighbours
+            })
+            .collect();
+
+        let (_, best) = trials.into_iter().min_by_key(|trial| trial.0).unwrap();
+
+        self.kick(best, &merged, len * MERGED_KICKS_PER_CITY, stop, improved)
+    }
+
+    fn distance(&self, from: usize, to: usize) -> i64 {
+        self.matrix[from][to] as i64
+    }
+
+    /// Runs LK from a random tour and then iterates it.
+    fn trial(
+        &self,
+        candidates: &[Vec<usize>],
+        stop: &AtomicBool,
+        improved: &(dyn Fn(u32, &[usize]) + Sync),
+    ) -> (u32, Vec<usize>) {
+        let len = self.matrix.len();
+
+        let mut order: Vec<usize> = (0..len).collect();
+        order.shuffle(&mut rand::rng());
+
+        let mut ring = Ring::new(order, len);
+        self.optimise(&mut ring, candidates, (0..len).collect(), stop);
+
+        self.kick(
+            ring.closed_from(0),
+            candidates,
+            len * KICKS_PER_CITY,
+            stop,
+            improved,
+        )
+    }
+
+    /// Iterated LK: perturbs the best local optimum with random double-bridge moves and
+    /// re-optimises around the broken edges, keeping the result whenever it is shorter.
+    fn kick(
+        &self,
+        mut best: Vec<usize>,
+        candidates: &[Vec<usize>],
+        kicks: usize,
+        stop: &AtomicBool,
+        improved: &(dyn Fn(u32, &[usize]) + Sync),
+    ) -> (u32, Vec<usize>) {
+        let len = self.matrix.len();
+        let mut best_cost = tour::cost(&self.matrix, &best);
+        improved(best_cost, &best);
+        let mut rng = rand::rng();
+
+        if len < 8 {
+            return (best_cost, best);
+        }
+
+        for _ in 0..kicks {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            // Segment-local double bridge: the three cuts lie close together so the kick
+            // reshapes a neighbourhood instead of scrambling the whole tour.
+            let a = rng.random_range(1..len - 2);
+            let b = rng.random_range(a + 1..(a + KICK_SEGMENT).min(len - 1));
+            let c = rng.random_range(b + 1..(b + KICK_SEGMENT).min(len));
+
+            let kicked: Vec<usize> = best[..a]
+                .iter()
+                .chain(&best[b..c])
+                .chain(&best[a..b])
+                .chain(&best[c..len])
+                .copied()
+                .collect();
+
+            let touched = [a, b, c]
+                .iter()
+                .flat_map(|&cut| [best[cut - 1], best[cut]])
+                .collect();
+
+            let mut ring = Ring::new(kicked, len);
+            self.optimise(&mut ring, candidates, touched, stop);
+
+            let path = ring.closed_from(0);
+            let cost = tour::cost(&self.matrix, &path);
+
+            if cost < best_cost {
+                best = path;
+                best_cost = cost;
+                improved(best_cost, &best);
+            }
+        }
+
+        (best_cost, best)
+    }
+
+    fn optimise(
+        &self,
+        ring: &mut Ring,
+        candidates: &[Vec<usize>],
+        active: Vec<usize>,
+        stop: &AtomicBool,
+    ) {
+        let mut queued = vec![false; self.matrix.len()];
+        let mut queue: VecDeque<usize> = active.into_iter().collect();
+        queue.iter().for_each(|&city| queued[city] = true);
+
+        while let Some(t1) = queue.pop_front() {
+            if stop.load(Ordering::Relaxed) {
+                return;
+            }
+
+            queued[t1] = false;
+
+            // Every LK step is a reversal, so asymmetric instances fall back to moves that
+            // keep each segment's direction.
+            let touched = if self.symmetric {
+                self.lk_move(ring, candidates, t1)
+                    .or_else(|| local_search::or_opt(&self.matrix, candidates, ring, t1, true))
+            } else {
+                local_search::or_opt(&self.matrix, candidates, ring, t1, false)
+                    .or_else(|| local_search::segment_swap(&self.matrix, candidates, ring, t1))
+            };
+
+            if let Some(touched) = touched {
+                touched.into_iter().for_each(|city: usize| {
+                    if !queued[city] {
+                        queued[city] = true;
+                        queue.push_back(city);
+                    }
+                });
+            }
+        }
+    }
+
+    fn lk_move(&self, ring: &mut Ring, candidates: &[Vec<usize>], t1: usize) -> Option<Vec<usize>> {
+        [true, false].into_iter().find_map(|forward| {
+            let t2 = ring.step(t1, forward);
+            let mut chain = Chain {
+                t1,
+                forward,
+                removed: vec![(t1, t2)],
+                added: vec![],
+            };
+
+            self.deepen(ring, candidates, &mut chain, t2, self.distance(t1, t2), 0)
+                .map(|_| {
+                    chain
+                        .removed
+                        .iter()
+                        .chain(&chain.added)
+                        .flat_map(|&(a, b)| [a, b])
+                        .collect()
+                })
+        })
+    }
+
+    /// One level of the variable-depth search. The tour is kept closed after every step by
+    /// the temporary edge `(t4, t1)`, so each level is a plain 2-opt reversal. Returns the
+    /// gain of the improving move, leaving the tour untouched when none is found.
+    fn deepen(
+        &self,
+        ring: &mut Ring,
+        candidates: &[Vec<usize>],
+        chain: &mut Chain,
+        t2: usize,
+        gain: i64,
+        best: i64,
+    ) -> Option<i64> {
+        let depth = chain.added.len();
+
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+
+        let mut options: Vec<(i64, usize, usize)> = candidates[t2]
+            .iter()
+            .filter_map(|&t3| {
+                let g1 = gain - self.distance(t2, t3);
+                let t4 = ring.step(t3, !chain.forward);
+
+                if g1 <= 0
+                    || t3 == chain.t1
+                    || t4 == t2
+                    || Chain::contains(&chain.removed, t2, t3)
+                    || Chain::contains(&chain.added, t3, t4)
+                {
+                    return None;
+                }
+
+                Some((g1 + self.distance(t3, t4), t3, t4))
+            })
+            .collect();
+
+        options.sort_by_key(|option| Reverse(option.0));
+        options.truncate(BREADTH.get(depth).copied().unwrap_or(1));
+
+        for (g2, t3, t4) in options {
+            chain.flip(ring, t2, t4);
+            chain.removed.push((t3, t4));
+            chain.added.push((t2, t3));
+
+            let close = g2 - self.distance(t4, chain.t1);
+
+            if let Some(found) = self.deepen(ring, candidates, chain, t4, g2, best.max(close)) {
+                return Some(found);
+            }
+
+            if close > best {
+                return Some(close);
+            }
+
+            chain.removed.pop();
+            chain.added.pop();
+            chain.flip(ring, t4, t2);
+        }
+
+        None
+    }
+
+    /// Ranks edges by alpha-nearness: how much longer the minimum 1-tree becomes when the
+    /// edge is forced into it.
+    fn alpha_candidates(&self) -> Vec<Vec<usize>> {
+        let len = self.matrix.len();
+
+        // Prim's MST over every city except the special node 0.
+        let mut parent = vec![usize::MAX; len];
+        let mut key = vec![u32::MAX; len];
+        let mut in_tree = vec![false; len];
+        let mut order = Vec::with_capacity(len - 1);
+
+        key[1] = 0;
+
+        for _ in 1..len {
+            let u = (1..len)
+                .filter(|&city| !in_tree[city])
+                .min_by_key(|&city| key[city])
+                .unwrap();
+
+            in_tree[u] = true;
+            order.push(u);
+
+            for v in 1..len {
+                if !in_tree[v] && self.matrix[u][v] < key[v] {
+                    key[v] = self.matrix[u][v];
+                    parent[v] = u;
+                }
+            }
+        }
+
+        // beta[i][j] is the longest edge on the tree path between i and j, filled in the
+        // order the cities joined the tree so the parent's row is always complete.
+        let mut beta = vec![vec![0u32; len]; len];
+
+        for (idx, &v) in order.iter().enumerate().skip(1) {
+            let p = parent[v];
+
+            for &u in &order[..idx] {
+                let value = beta[u][p].max(self.matrix[p][v]);
+
+                beta[u][v] = value;
+                beta[v][u] = value;
+            }
+        }
+
+        let mut special: Vec<u32> = (1..len).map(|city| self.matrix[0][city]).collect();
+        special.sort();
+        let second = special[1];
+
+        (0..len)
+            .map(|city| {
+                let alpha = |other: usize| {
+                    let distance = self.matrix[city][other];
+
+                    if city == 0 || other == 0 {
+                        distance.saturating_sub(second)
+                    } else {
+                        distance.saturating_sub(beta[city][other])
+                    }
+                };
+
+                let mut others: Vec<usize> = (0..len).filter(|&other| other != city).collect();
+                others.sort_by_key(|&other| (alpha(other), self.matrix[city][other]));
+                others.truncate(CANDIDATES);
+                others
+            })
+            .collect()
+    }
+}
+
+struct Chain {
+    t1: usize,
+    forward: bool,
+    removed: Vec<(usize, usize)>,
+    added: Vec<(usize, usize)>,
+}
+
+impl Chain {
+    fn contains(edges: &[(usize, usize)], a: usize, b: usize) -> bool {
+        edges.contains(&(a, b)) || edges.contains(&(b, a))
+    }
+
+    /// Reverses the segment between `from` and `to` in the direction the chain walks.
+    fn flip(&self, ring: &mut Ring, from: usize, to: usize) {
+        if self.forward {
+            ring.reverse(from, to);
+        } else {
+            ring.reverse(to, from);
+        }
+    }
+}
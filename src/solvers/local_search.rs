@@ -0,0 +1,303 @@
+use std::collections::VecDeque;
+
+use super::tour::{self, Ring};
+
+const CANDIDATES: usize = 10;
+const MAX_SEGMENT: usize = 3;
+
+pub struct LocalSearchSolver {
+    matrix: Vec<Vec<u32>>,
+    neighbours: Vec<Vec<usize>>,
+    symmetric: bool,
+}
+
+impl LocalSearchSolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        let neighbours = tour::candidate_lists(&matrix, CANDIDATES);
+
+        Self {
+            symmetric: tour::is_symmetric(&matrix),
+            matrix,
+            neighbours,
+        }
+    }
+}
+
+impl LocalSearchSolver {
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        if self.matrix.is_empty() {
+            return (0, vec![]);
+        }
+
+        self.improve(&tour::nearest_neighbour(&self.matrix, 0))
+    }
+
+    /// Polishes an existing closed tour (first city repeated at the end) with 2-opt, Or-opt
+    /// and 3-opt moves until no improving move is left. The tour keeps its starting city.
+    /// On asymmetric instances only moves that keep every segment's direction are used.
+    pub fn improve(&self, path: &[usize]) -> (u32, Vec<usize>) {
+        let order = match path {
+            [first, .., last] if first == last => path[..path.len() - 1].to_vec(),
+            _ => path.to_vec(),
+        };
+
+        if order.len() < 4 {
+            return (tour::cost(&self.matrix, path), path.to_vec());
+        }
+
+        let start = order[0];
+        let mut ring = Ring::new(order, self.matrix.len());
+
+        // Don't-look bits: only cities whose surrounding edges changed are queued again.
+        let mut queued = vec![false; self.matrix.len()];
+        let mut queue: VecDeque<usize> = ring.cities().iter().copied().collect();
+        queue.iter().for_each(|&city| queued[city] = true);
+
+        while let Some(city) = queue.pop_front() {
+            queued[city] = false;
+
+            let touched = if self.symmetric {
+                self.two_opt(&mut ring, city)
+                    .or_else(|| or_opt(&self.matrix, &self.neighbours, &mut ring, city, true))
+                    .or_else(|| self.three_opt(&mut ring, city))
+            } else {
+                or_opt(&self.matrix, &self.neighbours, &mut ring, city, false)
+                    .or_else(|| segment_swap(&self.matrix, &self.neighbours, &mut ring, city))
+            };
+
+            if let Some(touched) = touched {
+                touched.into_iter().for_each(|city| {
+                    if !queued[city] {
+                        queued[city] = true;
+                        queue.push_back(city);
+                    }
+                });
+            }
+        }
+
+        let closed = ring.closed_from(start);
+
+        (tour::cost(&self.matrix, &closed), closed)
+    }
+
+    fn distance(&self, from: usize, to: usize) -> i64 {
+        self.matrix[from][to] as i64
+    }
+
+    fn two_opt(&self, ring: &mut Ring, a: usize) -> Option<Vec<usize>> {
+        for forward in [true, false] {
+            let b = ring.step(a, forward);
+            let removed = self.distance(a, b);
+
+            for &c in &self.neighbours[a] {
+                if !ring.contains(c) {
+                    continue;
+                }
+
+                let g1 = removed - self.distance(a, c);
+
+                if g1 <= 0 {
+                    break;
+                }
+
+                let d = ring.step(c, forward);
+
+                if c == b || d == a {
+                    continue;
+                }
+
+                if g1 + self.distance(c, d) - self.distance(b, d) > 0 {
+                    if forward {
+                        ring.reverse(b, c);
+                    } else {
+                        ring.reverse(a, d);
+                    }
+
+                    return Some(vec![a, b, c, d]);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Sequential 3-opt move that reverses two adjacent segments: `a b..c d..e f` becomes
+    /// `a c..b e..d f`.
+    fn three_opt(&self, ring: &mut Ring, a: usize) -> Option<Vec<usize>> {
+        let b = ring.next(a);
+        let removed = self.distance(a, b);
+
+        for &c in &self.neighbours[a] {
+            if !ring.contains(c) {
+                continue;
+            }
+
+            let g1 = removed - self.distance(a, c);
+
+            if g1 <= 0 {
+                break;
+            }
+
+            let d = ring.next(c);
+
+            if c == b || d == a {
+                continue;
+            }
+
+            let g1 = g1 + self.distance(c, d);
+
+            for &e in &self.neighbours[b] {
+                if !ring.contains(e) {
+                    continue;
+                }
+
+                let g2 = g1 - self.distance(b, e);
+
+                if g2 <= 0 {
+                    break;
+                }
+
+                if ring.offset(a, e) < ring.offset(a, d) {
+                    continue;
+                }
+
+                let f = ring.next(e);
+
+                if g2 + self.distance(e, f) - self.distance(d, f) > 0 {
+                    ring.reverse(b, c);
+                    ring.reverse(d, e);
+
+                    return Some(vec![a, b, c, d, e, f]);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Moves a segment of up to three cities starting at `first` between two cities close to
+/// one of its ends, optionally reversing it when `reversible`.
+pub fn or_opt(
+    matrix: &[Vec<u32>],
+    neighbours: &[Vec<usize>],
+    ring: &mut Ring,
+    first: usize,
+    reversible: bool,
+) -> Option<Vec<usize>> {
+    let distance = |from: usize, to: usize| matrix[from][to] as i64;
+    let len = ring.len();
+    let mut last = first;
+
+    for length in 1..=MAX_SEGMENT {
+        if length + 3 > len {
+            break;
+        }
+
+        if length > 1 {
+            last = ring.next(last);
+        }
+
+        let prev = ring.prev(first);
+        let next = ring.next(last);
+        let removal = distance(prev, first) + distance(last, next) - distance(prev, next);
+
+        if removal <= 0 {
+            continue;
+        }
+
+        let in_segment = |ring: &Ring, city: usize| ring.offset(first, city) < length;
+
+        for end in [first, last] {
+            for &c in &neighbours[end] {
+                if !ring.contains(c) || in_segment(ring, c) {
+                    continue;
+                }
+
+                if distance(end, c) >= removal {
+                    break;
+                }
+
+                for (x, y) in [(ring.prev(c), c), (c, ring.next(c))] {
+                    if in_segment(ring, x) || in_segment(ring, y) {
+                        continue;
+                    }
+
+                    let kept = distance(x, y);
+                    let straight = distance(x, first) + distance(last, y) - kept;
+                    let reversed = distance(x, last) + distance(first, y) - kept;
+                    let reverse = reversible && reversed < straight;
+
+                    if removal - if reverse { reversed } else { straight } > 0 {
+                        ring.move_segment(first, last, x, reverse);
+
+                        return Some(vec![prev, next, x, y, first, last]);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Orientation-preserving 3-opt move that swaps two adjacent segments: `a b..c d..e f`
+/// becomes `a d..e b..c f`. Every city keeps its direction, so it is safe on asymmetric
+/// instances.
+pub fn segment_swap(
+    matrix: &[Vec<u32>],
+    neighbours: &[Vec<usize>],
+    ring: &mut Ring,
+    a: usize,
+) -> Option<Vec<usize>> {
+    let distance = |from: usize, to: usize| matrix[from][to] as i64;
+    let b = ring.next(a);
+    let removed = distance(a, b);
+
+    for &d in &neighbours[a] {
+        if !ring.contains(d) {
+            continue;
+        }
+
+        let g1 = removed - distance(a, d);
+
+        if g1 <= 0 {
+            break;
+        }
+
+        if d == b {
+            continue;
+        }
+
+        let c = ring.prev(d);
+        let g1 = g1 + distance(c, d);
+
+        for &f in &neighbours[c] {
+            if !ring.contains(f) {
+                continue;
+            }
+
+            let g2 = g1 - distance(c, f);
+
+            if g2 <= 0 {
+                break;
+            }
+
+            if ring.offset(a, f) <= ring.offset(a, d) {
+                continue;
+            }
+
+            let e = ring.prev(f);
+
+            if g2 + distance(e, f) - distance(e, b) > 0 {
+                ring.reverse(b, e);
+                ring.reverse(e, d);
+                ring.reverse(c, b);
+
+                return Some(vec![a, b, c, d, e, f]);
+            }
+        }
+    }
+
+    None
+}
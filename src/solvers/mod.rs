@@ -1,2 +1,16 @@
+pub mod aco;
+pub mod annealing;
+pub mod cvrp;
 pub mod dp;
 pub mod ga;
+pub mod gtsp;
+pub mod lin_kernighan;
+pub mod local_search;
+pub mod mtsp;
+pub mod orienteering;
+pub mod portfolio;
+pub mod precedence;
+pub mod route;
+pub mod tabu;
+pub mod time_windows;
+mod tour;
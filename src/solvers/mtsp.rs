@@ -0,0 +1,250 @@
+use std::fmt;
+
+use rand::Rng;
+use rand::seq::{SliceRandom, index};
+
+use super::local_search::LocalSearchSolver;
+use super::tour;
+
+const ELITISM: usize = 2;
+const TOURNAMENT: usize = 3;
+const CROSSOVER_RATE: f64 = 0.9;
+const MUTATION_RATE: f64 = 0.3;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Objective {
+    TotalDistance,
+    /// Balances the workload: the longest route is minimised first and the total second.
+    LongestRoute,
+}
+
+impl Objective {
+    pub const ALL: [Objective; 2] = [Objective::TotalDistance, Objective::LongestRoute];
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Objective::TotalDistance => write!(f, "Total distance"),
+            Objective::LongestRoute => write!(f, "Longest route"),
+        }
+    }
+}
+
+/// Two-part chromosome: every city that is not a depot in visiting order, and how many of
+/// them each salesman takes in turn.
+#[derive(Clone)]
+struct Individual {
+    order: Vec<usize>,
+    counts: Vec<usize>,
+    fitness: (u32, u32),
+}
+
+/// Multiple travelling salesmen: every city is visited by exactly one of the salesmen, each
+/// of whom leaves from and returns to their own depot.
+pub struct MultiSolver {
+    matrix: Vec<Vec<u32>>,
+    local_search: LocalSearchSolver,
+    pub salesmen: usize,
+    /// Depots handed to the salesmen in turn, so a single depot is shared by all of them.
+    pub depots: Vec<usize>,
+    pub objective: Objective,
+    pub population: usize,
+    pub generations: usize,
+}
+
+impl MultiSolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        Self {
+            local_search: LocalSearchSolver::new(matrix.clone()),
+            matrix,
+            salesmen: 3,
+            depots: vec![0],
+            objective: Objective::LongestRoute,
+            population: 200,
+            generations: 300,
+        }
+    }
+}
+
+impl MultiSolver {
+    /// Returns the objective value and one closed route per salesman, from and back to
+    /// their depot.
+    pub fn solve(&self) -> (u32, Vec<Vec<usize>>) {
+        // Every salesman visits at least one city. A salesman dropped for want of cities
+        // leaves their depot to be visited by the others.
+        let mut salesmen = self.salesmen;
+        let customers: Vec<usize> = loop {
+            let depots: Vec<usize> = (0..salesmen).map(|salesman| self.depot(salesman)).collect();
+            let customers: Vec<usize> = (0..self.matrix.len())
+                .filter(|city| !depots.contains(city))
+                .collect();
+
+            if salesmen <= customers.len() {
+                break customers;
+            }
+
+            salesmen -= 1;
+        };
+
+        if salesmen == 0 {
+            return (0, vec![]);
+        }
+
+        let mut rng = rand::rng();
+
+        let mut population: Vec<Individual> = (0..self.population.max(ELITISM + 1))
+            .map(|_| {
+                let mut order = customers.clone();
+                order.shuffle(&mut rng);
+
+                self.evaluate(Individual {
+                    order,
+                    counts: random_counts(customers.len(), salesmen, &mut rng),
+                    fitness: (0, 0),
+                })
+            })
+            .collect();
+
+        population.sort_by_key(|individual| individual.fitness);
+
+        for generation in 0..self.generations {
+            let mut next: Vec<Individual> = population[..ELITISM].to_vec();
+
+            while next.len() < population.len() {
+                let parent_1 = tournament(&population, &mut rng);
+                let parent_2 = tournament(&population, &mut rng);
+
+                let mut child = if rng.random::<f64>() < CROSSOVER_RATE {
+                    Individual {
+                        order: tour::order_crossover(&parent_1.order, &parent_2.order, &mut rng),
+                        counts: if rng.random::<bool>() {
+                            parent_1.counts.clone()
+                        } else {
+                            parent_2.counts.clone()
+                        },
+                        fitness: (0, 0),
+                    }
+                } else {
+                    parent_1.clone()
+                };
+
+                if rng.random::<f64>() < MUTATION_RATE && child.order.len() > 2 {
+                    let len = child.order.len();
+                    let (i, j) = (rng.random_range(0..len), rng.random_range(0..len));
+                    child.order[i.min(j)..=i.max(j)].reverse();
+                }
+
+                // Hands one city from a salesman to another, shifting the route boundaries.
+                if rng.random::<f64>() < MUTATION_RATE && salesmen > 1 {
+                    let from = rng.random_range(0..salesmen);
+                    let to = rng.random_range(0..salesmen);
+
+                    if from != to && child.counts[from] > 1 {
+                        child.counts[from] -= 1;
+                        child.counts[to] += 1;
+                    }
+                }
+
+                next.push(self.evaluate(child));
+            }
+
+            next.sort_by_key(|individual| individual.fitness);
+            population = next;
+
+            if generation % 100 == 0 {
+                println!("GENERATION {generation}: {}", population[0].fitness.0);
+            }
+        }
+
+        let routes = self.decode(&population[0]);
+
+        let lengths: Vec<u32> = routes.iter().map(|route| self.length(route)).collect();
+        let (total, longest) = (lengths.iter().sum(), *lengths.iter().max().unwrap());
+
+        println!("MTSP: {salesmen} salesmen, total {total}, longest {longest}");
+
+        match self.objective {
+            Objective::TotalDistance => (total, routes),
+            Objective::LongestRoute => (longest, routes),
+        }
+    }
+
+    pub fn length(&self, route: &[usize]) -> u32 {
+        tour::cost(&self.matrix, route)
+    }
+
+    fn depot(&self, salesman: usize) -> usize {
+        self.depots[salesman % self.depots.len()]
+    }
+
+    fn decode(&self, individual: &Individual) -> Vec<Vec<usize>> {
+        let mut rest = individual.order.as_slice();
+
+        individual
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(salesman, &count)| {
+                let (cities, tail) = rest.split_at(count);
+                rest = tail;
+
+                let depot = self.depot(salesman);
+                let mut route = vec![depot];
+                route.extend_from_slice(cities);
+                route.push(depot);
+                route
+            })
+            .collect()
+    }
+
+    /// Polishes every route with local search, writes the polished routes back into the
+    /// chromosome and scores it.
+    fn evaluate(&self, mut individual: Individual) -> Individual {
+        let routes: Vec<Vec<usize>> = self
+            .decode(&individual)
+            .iter()
+            .map(|route| self.local_search.improve(route).1)
+            .collect();
+
+        individual.order = routes
+            .iter()
+            .flat_map(|route| &route[1..route.len() - 1])
+            .copied()
+            .collect();
+
+        let lengths: Vec<u32> = routes.iter().map(|route| self.length(route)).collect();
+
+        let (total, longest) = (lengths.iter().sum(), *lengths.iter().max().unwrap());
+
+        individual.fitness = match self.objective {
+            Objective::TotalDistance => (total, longest),
+            Objective::LongestRoute => (longest, total),
+        };
+
+        individual
+    }
+}
+
+/// Splits `cities` into `parts` non-empty runs of random lengths.
+fn random_counts(cities: usize, parts: usize, rng: &mut impl Rng) -> Vec<usize> {
+    let mut cuts: Vec<usize> = index::sample(rng, cities - 1, parts - 1)
+        .into_iter()
+        .map(|cut| cut + 1)
+        .collect();
+
+    cuts.sort();
+    cuts.push(cities);
+
+    let mut last = 0;
+    cuts.into_iter()
+        .map(|cut| cut - std::mem::replace(&mut last, cut))
+        .collect()
+}
+
+fn tournament<'a>(population: &'a [Individual], rng: &mut impl Rng) -> &'a Individual {
+    (0..TOURNAMENT)
+        .map(|_| &population[rng.random_range(0..population.len())])
+        .min_by_key(|individual| individual.fitness)
+        .unwrap()
+}
@@ -0,0 +1,440 @@
+use std::fmt;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::local_search::LocalSearchSolver;
+use super::tour;
+
+/// Most cities besides the depot solved exactly; the DP table holds `2^cities · cities`
+/// entries.
+const DP_MAX_CITIES: usize = 20;
+const ELITISM: usize = 2;
+const TOURNAMENT: usize = 3;
+const CROSSOVER_RATE: f64 = 0.9;
+const MUTATION_RATE: f64 = 0.3;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Variant {
+    /// Collects the most prize on a tour no longer than the budget.
+    Orienteering,
+    /// Visits any cities, paying a penalty for every city it skips, and minimises the
+    /// tour length plus the penalties.
+    PrizeCollecting,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 2] = [Variant::Orienteering, Variant::PrizeCollecting];
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::Orienteering => write!(f, "Orienteering (budget)"),
+            Variant::PrizeCollecting => write!(f, "Prize-collecting (penalties)"),
+        }
+    }
+}
+
+/// Closed tours from the depot that visit only some of the cities, chosen by the prize
+/// they carry.
+pub struct OrienteeringSolver {
+    matrix: Vec<Vec<u32>>,
+    prizes: Vec<u32>,
+    penalties: Vec<u32>,
+    local_search: LocalSearchSolver,
+    pub depot: usize,
+    pub variant: Variant,
+    /// Longest tour allowed when orienteering.
+    pub budget: u32,
+    pub population: usize,
+    pub generations: usize,
+}
+
+impl OrienteeringSolver {
+    pub fn new(matrix: Vec<Vec<u32>>, prizes: Vec<u32>, penalties: Vec<u32>) -> Self {
+        Self {
+            local_search: LocalSearchSolver::new(matrix.clone()),
+            matrix,
+            prizes,
+            penalties,
+            depot: 0,
+            variant: Variant::Orienteering,
+            budget: 5000,
+            population: 100,
+            generations: 300,
+        }
+    }
+}
+
+impl OrienteeringSolver {
+    /// Returns the tour from and back to the depot, with its length when orienteering or
+    /// its length plus the penalties of the skipped cities otherwise.
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        let customers = self.customers();
+        let exact = customers.len() <= DP_MAX_CITIES;
+
+        println!(
+            "{}: {} cities besides the depot, solved {}",
+            match self.variant {
+                Variant::Orienteering => "ORIENTEERING",
+                Variant::PrizeCollecting => "PRIZE-COLLECTING",
+            },
+            customers.len(),
+            if exact { "exactly" } else { "with the GA" }
+        );
+
+        let route = if exact {
+            self.exact(&customers)
+        } else {
+            self.genetic(&customers)
+        };
+
+        let length = self.length(&route);
+
+        match self.variant {
+            Variant::Orienteering => (length, route),
+            Variant::PrizeCollecting => (length + self.penalty(&route), route),
+        }
+    }
+
+    pub fn length(&self, route: &[usize]) -> u32 {
+        tour::cost(&self.matrix, route)
+    }
+
+    /// Prize collected along `route`.
+    pub fn prize(&self, route: &[usize]) -> u32 {
+        self.customers()
+            .into_iter()
+            .filter(|city| route.contains(city))
+            .map(|city| self.prizes[city])
+            .sum()
+    }
+
+    /// Penalties of the cities `route` skips.
+    pub fn penalty(&self, route: &[usize]) -> u32 {
+        self.customers()
+            .into_iter()
+            .filter(|city| !route.contains(city))
+            .map(|city| self.penalties[city])
+            .sum()
+    }
+
+    /// Cities besides the depot.
+    pub fn customers(&self) -> Vec<usize> {
+        (0..self.matrix.len())
+            .filter(|&city| city != self.depot)
+            .collect()
+    }
+
+    /// What a city is worth in the objective: its prize or its penalty.
+    fn value(&self, city: usize) -> u32 {
+        match self.variant {
+            Variant::Orienteering => self.prizes[city],
+            Variant::PrizeCollecting => self.penalties[city],
+        }
+    }
+
+    /// Ranks a closed tour of the given length that leaves out cities worth `missed`;
+    /// lower is better.
+    fn score(&self, length: u32, missed: u32) -> (u32, u32) {
+        match self.variant {
+            Variant::Orienteering if length > self.budget => (u32::MAX, length),
+            Variant::Orienteering => (missed, length),
+            Variant::PrizeCollecting => (length.saturating_add(missed), length),
+        }
+    }
+
+    /// Held-Karp over every subset of cities, which also gives the shortest tour through
+    /// each subset; the best scoring subset wins. Orienteering never extends a path past
+    /// the budget.
+    fn exact(&self, customers: &[usize]) -> Vec<usize> {
+        let m = customers.len();
+        let size = 1 << m;
+        let full = size - 1;
+
+        let mut dp = vec![vec![u32::MAX; m]; size];
+        let mut worth = vec![0; size];
+
+        customers.iter().enumerate().for_each(|(b, &city)| {
+            dp[1 << b][b] = self.matrix[self.depot][city];
+        });
+
+        for mask in 1..size {
+            let low = mask.trailing_zeros() as usize;
+            worth[mask] = worth[mask & (mask - 1)] + self.value(customers[low]);
+
+            for u in 0..m {
+                let reached = dp[mask][u];
+
+                if reached == u32::MAX
+                    || (self.variant == Variant::Orienteering && reached > self.budget)
+                {
+                    continue;
+                }
+
+                for v in 0..m {
+                    if mask & (1 << v) != 0 {
+                        continue;
+                    }
+
+                    let next = mask | (1 << v);
+                    let cost = reached.saturating_add(self.matrix[customers[u]][customers[v]]);
+
+                    if cost < dp[next][v] {
+                        dp[next][v] = cost;
+                    }
+                }
+            }
+        }
+
+        // Staying at the depot is always a tour.
+        let mut best = (self.score(0, worth[full]), 0, 0);
+
+        for mask in 1..size {
+            for u in 0..m {
+                if dp[mask][u] == u32::MAX {
+                    continue;
+                }
+
+                let length = dp[mask][u].saturating_add(self.matrix[customers[u]][self.depot]);
+                let score = self.score(length, worth[full] - worth[mask]);
+
+                if score < best.0 {
+                    best = (score, mask, u);
+                }
+            }
+        }
+
+        let (_, mut mask, mut city) = best;
+        let mut route = vec![self.depot];
+
+        while mask != 0 {
+            route.push(customers[city]);
+
+            let prev_mask = mask ^ (1 << city);
+            let prev = (0..m).find(|&u| {
+                prev_mask & (1 << u) != 0
+                    && dp[prev_mask][u].saturating_add(self.matrix[customers[u]][customers[city]])
+                        == dp[mask][city]
+            });
+
+            mask = prev_mask;
+            city = prev.unwrap_or(0);
+        }
+
+        route.push(self.depot);
+        route.reverse();
+        route
+    }
+
+    /// GA over tours of any length: crossover keeps a slice of one parent and appends the
+    /// other parent's cities, mutation drops, adds or reverses cities, and every child is
+    /// polished and repaired until it fits the objective.
+    fn genetic(&self, customers: &[usize]) -> Vec<usize> {
+        let mut rng = rand::rng();
+
+        let mut population: Vec<(u32, u32, Vec<usize>)> = (0..self.population.max(ELITISM + 1))
+            .map(|_| {
+                let mut cities = customers.to_vec();
+                cities.shuffle(&mut rng);
+                cities.truncate(rng.random_range(0..=cities.len()));
+
+                self.evaluate(cities)
+            })
+            .collect();
+
+        population.sort_by_key(|individual| (individual.0, individual.1));
+
+        for generation in 0..self.generations {
+            let mut next = population[..ELITISM].to_vec();
+
+            while next.len() < population.len() {
+                let parent_1 = tournament(&population, &mut rng);
+                let parent_2 = tournament(&population, &mut rng);
+
+                let mut child = if rng.random::<f64>() < CROSSOVER_RATE {
+                    crossover(parent_1, parent_2, &mut rng)
+                } else {
+                    parent_1.to_vec()
+                };
+
+                if rng.random::<f64>() < MUTATION_RATE {
+                    mutate(&mut child, customers, &mut rng);
+                }
+
+                next.push(self.evaluate(child));
+            }
+
+            next.sort_by_key(|individual| (individual.0, individual.1));
+            population = next;
+
+            if generation % 100 == 0 {
+                println!("GENERATION {generation}: {}", population[0].0);
+            }
+        }
+
+        let mut route = population.swap_remove(0).2;
+        route.insert(0, self.depot);
+        route.push(self.depot);
+        route
+    }
+
+    /// Polishes the tour through `cities`, then drops and adds cities by their worth per
+    /// kilometre until no change helps. Returns the score and the cities in tour order.
+    fn evaluate(&self, cities: Vec<usize>) -> (u32, u32, Vec<usize>) {
+        let mut route = vec![self.depot];
+        route.extend(cities);
+        route.push(self.depot);
+
+        route = self.local_search.improve(&route).1;
+        self.repair(&mut route);
+
+        let length = self.length(&route);
+        let missed = self
+            .customers()
+            .into_iter()
+            .filter(|city| !route.contains(city))
+            .map(|city| self.value(city))
+            .sum();
+        let (score, length) = self.score(length, missed);
+
+        (score, length, route[1..route.len() - 1].to_vec())
+    }
+
+    fn repair(&self, route: &mut Vec<usize>) {
+        let mut length = self.length(route);
+
+        // Drops the city that gives up the least worth per kilometre saved, while the tour
+        // is over budget or a city costs more to reach than its penalty.
+        loop {
+            let drop = (1..route.len() - 1)
+                .map(|idx| {
+                    let saving = (self.matrix[route[idx - 1]][route[idx]]
+                        + self.matrix[route[idx]][route[idx + 1]])
+                        .saturating_sub(self.matrix[route[idx - 1]][route[idx + 1]]);
+
+                    (idx, saving)
+                })
+                .filter(|&(idx, saving)| match self.variant {
+                    Variant::Orienteering => length > self.budget,
+                    Variant::PrizeCollecting => saving > self.value(route[idx]),
+                })
+                .min_by(|a, b| {
+                    let ratio = |&(idx, saving): &(usize, u32)| {
+                        self.value(route[idx]) as f64 / (saving as f64 + 1.0)
+                    };
+                    ratio(a).total_cmp(&ratio(b))
+                });
+
+            match drop {
+                Some((idx, _)) => {
+                    route.remove(idx);
+                    length = self.length(route);
+                }
+                None => break,
+            }
+        }
+
+        // Adds the city that brings the most worth per extra kilometre, as long as it fits
+        // the budget or costs less than its penalty.
+        let mut visited = vec![false; self.matrix.len()];
+        route.iter().for_each(|&city| visited[city] = true);
+
+        loop {
+            let add = (0..self.matrix.len())
+                .filter(|&city| !visited[city])
+                .map(|city| {
+                    let (idx, extra) = (1..route.len())
+                        .map(|idx| {
+                            let extra = (self.matrix[route[idx - 1]][city]
+                                + self.matrix[city][route[idx]])
+                                .saturating_sub(self.matrix[route[idx - 1]][route[idx]]);
+
+                            (idx, extra)
+                        })
+                        .min_by_key(|&(_, extra)| extra)
+                        .unwrap();
+
+                    (city, idx, extra)
+                })
+                .filter(|&(city, _, extra)| match self.variant {
+                    Variant::Orienteering => length + extra <= self.budget && self.value(city) > 0,
+                    Variant::PrizeCollecting => extra < self.value(city),
+                })
+                .max_by(|a, b| {
+                    let ratio = |&(city, _, extra): &(usize, usize, u32)| {
+                        self.value(city) as f64 / (extra as f64 + 1.0)
+                    };
+                    ratio(a).total_cmp(&ratio(b))
+                });
+
+            match add {
+                Some((city, idx, _)) => {
+                    route.insert(idx, city);
+                    visited[city] = true;
+                    length = self.length(route);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Keeps a random slice of `donor` and appends the cities of `other` it lacks, in their
+/// order; repair trims the result back to size.
+fn crossover(donor: &[usize], other: &[usize], rng: &mut impl Rng) -> Vec<usize> {
+    if donor.is_empty() {
+        return other.to_vec();
+    }
+
+    let (i, j) = (
+        rng.random_range(0..donor.len()),
+        rng.random_range(0..donor.len()),
+    );
+    let mut child = donor[i.min(j)..=i.max(j)].to_vec();
+
+    let rest: Vec<usize> = other
+        .iter()
+        .filter(|city| !child.contains(city))
+        .copied()
+        .collect();
+    child.extend(rest);
+    child
+}
+
+/// Drops a city, adds one that is not visited, or reverses a stretch of the tour.
+fn mutate(cities: &mut Vec<usize>, customers: &[usize], rng: &mut impl Rng) {
+    match rng.random_range(0..3) {
+        0 if !cities.is_empty() => {
+            cities.remove(rng.random_range(0..cities.len()));
+        }
+        1 => {
+            let missing: Vec<usize> = customers
+                .iter()
+                .filter(|city| !cities.contains(city))
+                .copied()
+                .collect();
+
+            if let Some(&city) = missing.get(rng.random_range(0..missing.len().max(1))) {
+                cities.insert(rng.random_range(0..=cities.len()), city);
+            }
+        }
+        _ if cities.len() > 1 => {
+            let (i, j) = (
+                rng.random_range(0..cities.len()),
+                rng.random_range(0..cities.len()),
+            );
+            cities[i.min(j)..=i.max(j)].reverse();
+        }
+        _ => {}
+    }
+}
+
+fn tournament<'a>(population: &'a [(u32, u32, Vec<usize>)], rng: &mut impl Rng) -> &'a [usize] {
+    &(0..TOURNAMENT)
+        .map(|_| &population[rng.random_range(0..population.len())])
+        .min_by_key(|individual| (individual.0, individual.1))
+        .unwrap()
+        .2
+}
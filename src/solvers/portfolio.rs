@@ -0,0 +1,232 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::aco::AntColonySolver;
+use super::annealing::SimulatedAnnealingSolver;
+use super::dp::DPSolver;
+use super::lin_kernighan::LinKernighanSolver;
+use super::tabu::TabuSearchSolver;
+use super::tour;
+
+/// Largest DP table Held-Karp may allocate as a portfolio member.
+pub const DP_MEMORY_BUDGET: usize = 1 << 30;
+/// Tabu search scans the whole neighbourhood every iteration, which stops paying off on
+/// large instances.
+const TABU_MAX_CITIES: usize = 500;
+
+/// Told about every new best tour a member finds while it runs.
+type Improved<'a> = dyn Fn(u32, &[usize]) + Sync + 'a;
+type Run = Box<dyn FnOnce(&AtomicU32, &AtomicBool, &Improved) -> Finish + Send>;
+type Member = (&'static str, Run);
+
+/// What a member reports when it stops: a tour, if it found one, and whether finishing
+/// proves that the best tour known at that moment is optimal.
+struct Finish {
+    tour: Option<(u32, Vec<usize>)>,
+    optimal: bool,
+}
+
+struct Race {
+    cost: u32,
+    path: Vec<usize>,
+    winner: &'static str,
+    optimal: bool,
+    finished: usize,
+}
+
+/// Best tour found so far, improved by members as they run and when they finish. `bound`
+/// mirrors its cost so members can prune against it without taking the lock, and `stop`
+/// tells every member still running that the race is over.
+struct Incumbent {
+    bound: AtomicU32,
+    stop: AtomicBool,
+    race: Mutex<Race>,
+    changed: Condvar,
+}
+
+impl Incumbent {
+    /// Takes the tour if it beats the best one, lowering `bound` only once the tour is in
+    /// place, so a proof against the bound always has a tour of that cost to return.
+    fn improve(&self, race: &mut Race, name: &'static str, cost: u32, path: &[usize]) {
+        if cost < race.cost {
+            race.cost = cost;
+            race.path = path.to_vec();
+            race.winner = name;
+            self.bound.fetch_min(cost, Ordering::Relaxed);
+        }
+    }
+
+    /// A new best tour a member found while running; most offers lose to the bound and
+    /// never take the lock.
+    fn improved(&self, name: &'static str, cost: u32, path: &[usize]) {
+        if cost < self.bound.load(Ordering::Relaxed) {
+            let mut race = self.race.lock().unwrap();
+            self.improve(&mut race, name, cost, path);
+        }
+    }
+
+    fn offer(&self, name: &'static str, finish: Finish) {
+        let mut race = self.race.lock().unwrap();
+
+        if let Some((cost, path)) = &finish.tour {
+            self.improve(&mut race, name, *cost, path);
+        }
+
+        race.optimal |= finish.optimal;
+        race.finished += 1;
+        self.changed.notify_all();
+    }
+}
+
+pub struct PortfolioSolver {
+    matrix: Vec<Vec<u32>>,
+    pub deadline_secs: u64,
+}
+
+impl PortfolioSolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        Self {
+            matrix,
+            deadline_secs: 10,
+        }
+    }
+}
+
+impl PortfolioSolver {
+    /// Races every solver suited to the instance on the rayon pool and returns the first
+    /// proven optimum, or the best tour once all members finished or the deadline passed.
+    /// Members still running at that point are told to stop, free the pool as soon as
+    /// they notice, and are ignored.
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        let members = self.members();
+        let count = members.len();
+
+        let start = tour::nearest_neighbour(&self.matrix, 0);
+        let cost = tour::cost(&self.matrix, &start);
+
+        let incumbent = Arc::new(Incumbent {
+            bound: AtomicU32::new(cost),
+            stop: AtomicBool::new(false),
+            race: Mutex::new(Race {
+                cost,
+                path: start,
+                winner: "Nearest neighbour",
+                optimal: false,
+                finished: 0,
+            }),
+            changed: Condvar::new(),
+        });
+
+        members.into_iter().for_each(|(name, run)| {
+            let incumbent = Arc::clone(&incumbent);
+
+            rayon::spawn(move || {
+                let improved = |cost: u32, path: &[usize]| incumbent.improved(name, cost, path);
+                let finish = run(&incumbent.bound, &incumbent.stop, &improved);
+                incumbent.offer(name, finish);
+            });
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(self.deadline_secs);
+        let mut race = incumbent.race.lock().unwrap();
+
+        while !race.optimal && race.finished < count {
+            let now = Instant::now();
+
+            if now >= deadline {
+                break;
+            }
+
+            race = incumbent
+                .changed
+                .wait_timeout(race, deadline - now)
+                .unwrap()
+                .0;
+        }
+
+        incumbent.stop.store(true, Ordering::Relaxed);
+
+        println!("PORTFOLIO: {} of {count} solvers finished", race.finished);
+        println!(
+            "WINNER: {}{}",
+            race.winner,
+            if race.optimal {
+                " (proven optimal)"
+            } else {
+                ""
+            }
+        );
+
+        (race.cost, race.path.clone())
+    }
+
+    /// Picks the members worth running on this instance.
+    fn members(&self) -> Vec<Member> {
+        let cities = self.matrix.len();
+        let mut members: Vec<Member> = vec![];
+
+        let memory = DPSolver::memory(cities);
+
+        if memory <= DP_MEMORY_BUDGET {
+            let solver = DPSolver::new(self.matrix.clone());
+
+            members.push((
+                "Held-Karp",
+                // Finishing proves optimality only if it was not stopped first.
+                Box::new(move |bound, stop, _| Finish {
+                    tour: solver.solve_bounded(bound, stop),
+                    optimal: !stop.load(Ordering::Relaxed),
+                }),
+            ));
+        } else {
+            // The size saturates once it no longer fits in a usize.
+            let needed = if memory == usize::MAX {
+                format!("more than 2^{} bytes", usize::BITS)
+            } else {
+                format!("{} MB", memory / (1 << 20))
+            };
+
+            println!("PORTFOLIO: skipping Held-Karp, it needs {needed}");
+        }
+
+        let lk = LinKernighanSolver::new(self.matrix.clone());
+        members.push((
+            "Lin-Kernighan",
+            heuristic(move |stop, improved| lk.solve_until(stop, improved)),
+        ));
+
+        let aco = AntColonySolver::new(self.matrix.clone());
+        members.push((
+            "Ant Colony",
+            heuristic(move |stop, improved| aco.solve_until(stop, improved)),
+        ));
+
+        let sa = SimulatedAnnealingSolver::new(self.matrix.clone());
+        members.push((
+            "Simulated Annealing",
+            heuristic(move |stop, improved| sa.solve_until(stop, improved)),
+        ));
+
+        if cities <= TABU_MAX_CITIES {
+            let tabu = TabuSearchSolver::new(self.matrix.clone());
+            members.push((
+                "Tabu Search",
+                heuristic(move |stop, improved| tabu.solve_until(stop, improved)),
+            ));
+        }
+
+        members
+    }
+}
+
+/// A member that shares every new best tour as it finds it, so Held-Karp prunes against it
+/// straight away.
+fn heuristic(
+    solve: impl FnOnce(&AtomicBool, &Improved) -> (u32, Vec<usize>) + Send + 'static,
+) -> Run {
+    Box::new(move |_, stop, improved| Finish {
+        tour: Some(solve(stop, improved)),
+        optimal: false,
+    })
+}
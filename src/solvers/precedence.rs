@@ -0,0 +1,143 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::ops::Range;
+
+/// Pairs of cities where the first has to be visited before the second, along the path
+/// from its start. A closed tour counts its start, city 0, as visited first; cities are
+/// indexed by name, so that is the same city on every run.
+#[derive(PartialEq, Clone, Default)]
+pub struct Precedence {
+    pairs: Vec<(usize, usize)>,
+}
+
+impl Precedence {
+    pub fn new(pairs: Vec<(usize, usize)>) -> Self {
+        Self { pairs }
+    }
+
+    pub fn pairs(&self) -> &[(usize, usize)] {
+        &self.pairs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Whether the pairs can all hold at once, i.e. they form no cycle.
+    pub fn is_consistent(&self, cities: usize) -> bool {
+        let mut order: Vec<usize> = (0..cities).collect();
+        self.repair(&mut order, 0..cities);
+
+        self.violations(&order).is_empty()
+    }
+
+    /// For every city, the bitmask of cities that have to be visited before it. Only fits
+    /// as many cities as a `usize` has bits, far more than Held-Karp can handle anyway.
+    pub fn required(&self, cities: usize) -> Vec<usize> {
+        assert!(
+            cities <= usize::BITS as usize,
+            "precedence masks hold at most {} cities",
+            usize::BITS
+        );
+
+        let mut required = vec![0; cities];
+        self.pairs
+            .iter()
+            .for_each(|&(before, after)| required[after] |= 1 << before);
+
+        required
+    }
+
+    /// Pairs the path visits in the wrong order, or only partly.
+    pub fn violations(&self, path: &[usize]) -> Vec<(usize, usize)> {
+        let position = |city: usize| path.iter().position(|&other| other == city);
+
+        self.pairs
+            .iter()
+            .filter(
+                |&&(before, after)| match (position(before), position(after)) {
+                    (Some(before), Some(after)) => before > after,
+                    _ => true,
+                },
+            )
+            .copied()
+            .collect()
+    }
+
+    /// Reorders `path[interior]` so every pair inside it holds, moving as little as
+    /// possible: a topological sort that always places the earliest city it may. Pairs
+    /// with a city outside `interior` are left alone.
+    pub fn repair(&self, path: &mut [usize], interior: Range<usize>) {
+        if self.pairs.is_empty() || interior.is_empty() {
+            return;
+        }
+
+        let slice = &mut path[interior];
+        let len = slice.len();
+        let cities = slice.iter().max().map_or(0, |&max| max + 1);
+
+        let mut position = vec![usize::MAX; cities];
+        slice
+            .iter()
+            .enumerate()
+            .for_each(|(idx, &city)| position[city] = idx);
+
+        let inside = |city: usize| city < cities && position[city] != usize::MAX;
+        let pairs: Vec<(usize, usize)> = self
+            .pairs
+            .iter()
+            .filter(|&&(before, after)| inside(before) && inside(after))
+            .map(|&(before, after)| (position[before], position[after]))
+            .collect();
+
+        if pairs.iter().all(|&(before, after)| before < after) {
+            return;
+        }
+
+        let mut waiting = vec![0; len];
+        let mut successors: Vec<Vec<usize>> = vec![vec![]; len];
+        pairs.iter().for_each(|&(before, after)| {
+            waiting[after] += 1;
+            successors[before].push(after);
+        });
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..len)
+            .filter(|&idx| waiting[idx] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(len);
+
+        while let Some(Reverse(idx)) = ready.pop() {
+            order.push(idx);
+
+            successors[idx].iter().for_each(|&next| {
+                waiting[next] -= 1;
+
+                if waiting[next] == 0 {
+                    ready.push(Reverse(next));
+                }
+            });
+        }
+
+        // A cycle leaves its cities unplaced; they keep their relative order at the end.
+        let mut placed = vec![false; len];
+        order.iter().for_each(|&idx| placed[idx] = true);
+        order.extend((0..len).filter(|&idx| !placed[idx]));
+
+        let cities: Vec<usize> = order.iter().map(|&idx| slice[idx]).collect();
+        slice.copy_from_slice(&cities);
+    }
+}
+
+impl fmt::Display for Precedence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs: Vec<String> = self
+            .pairs
+            .iter()
+            .map(|(before, after)| format!("{before}>{after}"))
+            .collect();
+
+        write!(f, "{}", pairs.join(" "))
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// Shape of a solution. A closed tour starts and ends at city 0 and repeats it at the end.
+/// An open path starts at `start` and stops at `end`, or wherever is cheapest when `end`
+/// is `None`, visiting every city once.
+#[derive(PartialEq, Clone, Copy)]
+pub enum Route {
+    Closed,
+    Open { start: usize, end: Option<usize> },
+}
+
+impl Route {
+    pub fn start(&self) -> usize {
+        match self {
+            Route::Closed => 0,
+            Route::Open { start, .. } => *start,
+        }
+    }
+
+    /// Cities that may appear anywhere between the fixed ends, in index order.
+    pub fn movable(&self, cities: usize) -> Vec<usize> {
+        (0..cities)
+            .filter(|&city| match self {
+                Route::Closed => city != 0,
+                Route::Open { start, end } => city != *start && Some(city) != *end,
+            })
+            .collect()
+    }
+
+    /// Positions of a solution over `cities` that operators may change.
+    pub fn interior(&self, cities: usize) -> Range<usize> {
+        match self {
+            Route::Closed | Route::Open { end: None, .. } => 1..cities,
+            Route::Open { end: Some(_), .. } => 1..cities.saturating_sub(1).max(1),
+        }
+    }
+
+    pub fn random(&self, cities: usize, rng: &mut impl Rng) -> Vec<usize> {
+        let mut path = vec![self.start()];
+
+        let mut movable = self.movable(cities);
+        movable.shuffle(rng);
+        path.append(&mut movable);
+
+        match self {
+            Route::Closed => path.push(0),
+            Route::Open { end: Some(end), .. } => path.push(*end),
+            Route::Open { end: None, .. } => {}
+        }
+
+        path
+    }
+
+    /// Whether `path` visits each of `cities` exactly once in the shape of this route.
+    pub fn is_valid(&self, path: &[usize], cities: usize) -> bool {
+        let visits = match self {
+            Route::Closed if path.last() != Some(&0) => return false,
+            Route::Closed => &path[..path.len() - 1],
+            Route::Open { end: Some(end), .. } if path.last() != Some(end) => return false,
+            Route::Open { .. } => path,
+        };
+
+        let mut seen = vec![false; cities];
+
+        visits.len() == cities
+            && path.first() == Some(&self.start())
+            && visits
+                .iter()
+                .all(|&city| city < cities && !mem::replace(&mut seen[city], true))
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Route::Closed => write!(f, "closed"),
+            Route::Open { start, end: None } => write!(f, "open {start}"),
+            Route::Open {
+                start,
+                end: Some(end),
+            } => write!(f, "open {start} {end}"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::Rng;
+
+use super::tour;
+
+const ITERATIONS_PER_CITY: usize = 30;
+const BASE_TENURE: usize = 7;
+const DIVERSIFICATION: i64 = 2;
+const RESTART_STALL: usize = 200;
+
+#[derive(Clone, Copy)]
+enum Move {
+    TwoOpt,
+    Swap,
+}
+
+pub struct TabuSearchSolver {
+    matrix: Vec<Vec<u32>>,
+}
+
+impl TabuSearchSolver {
+    pub fn new(matrix: Vec<Vec<u32>>) -> Self {
+        Self { matrix }
+    }
+}
+
+impl TabuSearchSolver {
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        self.solve_until(&AtomicBool::new(false), &|_, _| {})
+    }
+
+    /// The best tour of the search, cut short when `stop` is set. `improved` is told about
+    /// every new best tour as soon as it is found.
+    pub fn solve_until(
+        &self,
+        stop: &AtomicBool,
+        improved: &(dyn Fn(u32, &[usize]) + Sync),
+    ) -> (u32, Vec<usize>) {
+        let len = self.matrix.len();
+        let mut path = tour::nearest_neighbour(&self.matrix, 0);
+        let mut cost = tour::cost(&self.matrix, &path) as i64;
+
+        if len < 4 {
+            return (cost as u32, path);
+        }
+
+        let mut best = (cost, path.clone());
+        let mut rng = rand::rng();
+
+        // Short-term memory: the iteration until which moving a pair of cities is tabu.
+        // Long-term memory: how often each pair has been moved.
+        let mut tabu_until = vec![vec![0usize; len]; len];
+        let mut frequency = vec![vec![0i64; len]; len];
+        let mut stall = 0;
+
+        for iteration in 1..=len * ITERATIONS_PER_CITY {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let mut chosen: Option<(i64, i64, Move, usize, usize)> = None;
+            let skew = tour::reversal_skew(&self.matrix, &path);
+
+            for i in 1..len - 1 {
+                for j in i + 1..len {
+                    let (a, b) = (path[i], path[j]);
+
+                    for kind in [Move::TwoOpt, Move::Swap] {
+                        let delta = self.delta(&path, &skew, kind, i, j);
+                        let aspired = cost + delta < best.0;
+
+                        if tabu_until[a][b] > iteration && !aspired {
+                            continue;
+                        }
+
+                        let score = if delta >= 0 {
+                            delta + DIVERSIFICATION * frequency[a][b]
+                        } else {
+                            delta
+                        };
+
+                        if chosen.is_none_or(|current| score < current.0) {
+                            chosen = Some((score, delta, kind, i, j));
+                        }
+                    }
+                }
+            }
+
+            let Some((_, delta, kind, i, j)) = chosen else {
+                continue;
+            };
+
+            let (a, b) = (path[i], path[j]);
+
+            match kind {
+                Move::TwoOpt => path[i..=j].reverse(),
+                Move::Swap => path.swap(i, j),
+            }
+
+            cost += delta;
+
+            let tenure = BASE_TENURE + rng.random_range(0..=len / 10);
+            tabu_until[a][b] = iteration + tenure;
+            tabu_until[b][a] = iteration + tenure;
+            frequency[a][b] += 1;
+            frequency[b][a] += 1;
+
+            if cost < best.0 {
+                best = (cost, path.clone());
+                improved(cost as u32, &path);
+                stall = 0;
+            } else {
+                stall += 1;
+            }
+
+            if stall > RESTART_STALL {
+                (cost, path) = best.clone();
+                stall = 0;
+            }
+        }
+
+        (best.0 as u32, best.1)
+    }
+
+    fn delta(&self, path: &[usize], skew: &[i64], kind: Move, i: usize, j: usize) -> i64 {
+        let d = |a: usize, b: usize| self.matrix[path[a]][path[b]] as i64;
+
+        match kind {
+            Move::TwoOpt => {
+                d(i - 1, j) + d(i, j + 1) - d(i - 1, i) - d(j, j + 1) + skew[j] - skew[i]
+            }
+            Move::Swap if j == i + 1 => {
+                d(i - 1, j) + d(j, i) + d(i, j + 1) - d(i - 1, i) - d(i, j) - d(j, j + 1)
+            }
+            Move::Swap => {
+                d(i - 1, j) + d(j, i + 1) + d(j - 1, i) + d(i, j + 1)
+                    - d(i - 1, i)
+                    - d(i, i + 1)
+                    - d(j - 1, j)
+                    - d(j, j + 1)
+            }
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::tour;
+
+/// States the exact search may create before it gives up and leaves the instance to the GA.
+const MAX_LABELS: usize = 2_000_000;
+/// Distance charged per unit of time a city is reached after its window closed.
+const LATENESS_PENALTY: u64 = 1000;
+const ELITISM: usize = 2;
+const TOURNAMENT: usize = 3;
+const CROSSOVER_RATE: f64 = 0.9;
+const MUTATION_RATE: f64 = 0.3;
+
+/// When a city can be served and for how long. Times share the unit of the travel times.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Window {
+    pub open: u32,
+    pub close: u32,
+    pub service: u32,
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Self {
+            open: 0,
+            close: u32::MAX,
+            service: 0,
+        }
+    }
+}
+
+/// One visit of a schedule: when the vehicle arrives, when service starts after waiting
+/// for the window to open, and how late it arrived after the window closed.
+pub struct Stop {
+    pub city: usize,
+    pub arrival: u32,
+    pub start: u32,
+    pub late: u32,
+}
+
+/// Non-dominated partial route in the exact search, linked to the label it extends.
+struct Label {
+    city: usize,
+    departure: u32,
+    cost: u32,
+    parent: usize,
+}
+
+/// TSP with time windows: the shortest closed tour from the depot that reaches every city
+/// before its window closes, waiting when it arrives early.
+pub struct TimeWindowSolver {
+    matrix: Vec<Vec<u32>>,
+    times: Vec<Vec<u32>>,
+    /// Shortest travel time between every two cities, over any detour. Travel times need
+    /// not obey the triangle inequality, so only these bound how soon a city can be reached.
+    fastest: Vec<Vec<u32>>,
+    windows: Vec<Window>,
+    /// City the tour starts from and returns to.
+    pub depot: usize,
+    pub population: usize,
+    pub generations: usize,
+}
+
+impl TimeWindowSolver {
+    /// `times` holds the travel times between cities; pass the distances again to treat
+    /// them as times.
+    pub fn new(matrix: Vec<Vec<u32>>, times: Vec<Vec<u32>>, windows: Vec<Window>) -> Self {
+        let len = times.len();
+        let mut fastest = times.clone();
+
+        // Floyd–Warshall.
+        (0..len).for_each(|via| {
+            (0..len).for_each(|i| {
+                (0..len).for_each(|j| {
+                    let detour = fastest[i][via].saturating_add(fastest[via][j]);
+
+                    if detour < fastest[i][j] {
+                        fastest[i][j] = detour;
+                    }
+                })
+            })
+        });
+
+        Self {
+            matrix,
+            times,
+            fastest,
+            windows,
+            depot: 0,
+            population: 100,
+            generations: 1000,
+        }
+    }
+}
+
+impl TimeWindowSolver {
+    /// Tries the exact search first and falls back to the GA when it has too many states
+    /// or finds no feasible tour.
+    pub fn solve(&self) -> (u32, Vec<usize>) {
+        if self.matrix.len() < 2 {
+            return (0, vec![self.depot; self.matrix.len() * 2]);
+        }
+
+        match self.exact() {
+            Some(found) => {
+                println!("TIME WINDOWS: optimal");
+                found
+            }
+            None => {
+                let path = self.genetic();
+                (tour::cost(&self.matrix, &path), path)
+            }
+        }
+    }
+
+    /// Arrival and service times along a closed tour that leaves its first city when that
+    /// city's window opens. The last stop is the return to the first city.
+    pub fn schedule(&self, path: &[usize]) -> Vec<Stop> {
+        let mut stops: Vec<Stop> = Vec::with_capacity(path.len());
+        let mut clock = 0;
+
+        for (idx, &city) in path.iter().enumerate() {
+            let window = self.windows[city];
+            let arrival = match idx {
+                0 => window.open,
+                _ => clock + self.times[path[idx - 1]][city],
+            };
+            let start = arrival.max(window.open);
+
+            stops.push(Stop {
+                city,
+                arrival,
+                start,
+                late: arrival.saturating_sub(window.close),
+            });
+
+            clock = start + window.service;
+        }
+
+        stops
+    }
+
+    /// Total time by which the tour misses its windows.
+    pub fn lateness(&self, path: &[usize]) -> u32 {
+        self.schedule(path).iter().map(|stop| stop.late).sum()
+    }
+
+    /// Label-setting Held-Karp: every set of visited cities and last city keeps only the
+    /// partial routes no other route beats on both departure time and distance. Routes
+    /// that can no longer reach a city still to visit before its window closes, even by
+    /// the fastest detour, are dropped.
+    fn exact(&self) -> Option<(u32, Vec<usize>)> {
+        let n = self.matrix.len();
+
+        // Visited sets are bitmasks.
+        if n > 32 {
+            return None;
+        }
+
+        let depot = self.windows[self.depot];
+        let mut labels = vec![Label {
+            city: self.depot,
+            departure: depot.open + depot.service,
+            cost: 0,
+            parent: usize::MAX,
+        }];
+        let mut layer: HashMap<(u32, usize), Vec<usize>> =
+            HashMap::from([((1 << self.depot, self.depot), vec![0])]);
+
+        for _ in 1..n {
+            let mut next: HashMap<(u32, usize), Vec<usize>> = HashMap::new();
+
+            for (&(mask, _), front) in &layer {
+                for &idx in front {
+                    for v in 0..n {
+                        if mask & (1 << v) != 0 {
+                            continue;
+                        }
+
+                        let label = &labels[idx];
+                        let window = self.windows[v];
+                        let arrival = label.departure + self.times[label.city][v];
+
+                        if arrival > window.close {
+                            continue;
+                        }
+
+                        let departure = arrival.max(window.open) + window.service;
+                        let next_mask = mask | (1 << v);
+
+                        // Even the fastest way to a city still to visit arrives too late.
+                        let stranded = (0..n).any(|u| {
+                            next_mask & (1 << u) == 0
+                                && departure.saturating_add(self.fastest[v][u])
+                                    > self.windows[u].close
+                        });
+
+                        if stranded {
+                            continue;
+                        }
+
+                        let cost = label.cost + self.matrix[label.city][v];
+                        let front = next.entry((next_mask, v)).or_default();
+
+                        let dominated = front.iter().any(|&other| {
+                            labels[other].departure <= departure && labels[other].cost <= cost
+                        });
+
+                        if dominated {
+                            continue;
+                        }
+
+                        front.retain(|&other| {
+                            labels[other].departure < departure || labels[other].cost < cost
+                        });
+                        front.push(labels.len());
+                        labels.push(Label {
+                            city: v,
+                            departure,
+                            cost,
+                            parent: idx,
+                        });
+
+                        if labels.len() > MAX_LABELS {
+                            println!("TIME WINDOWS: too many states for the exact search");
+                            return None;
+                        }
+                    }
+                }
+            }
+
+            layer = next;
+        }
+
+        let (cost, mut idx) = layer
+            .values()
+            .flatten()
+            .filter(|&&idx| {
+                let label = &labels[idx];
+                label.departure + self.times[label.city][self.depot] <= depot.close
+            })
+            .map(|&idx| {
+                (
+                    labels[idx].cost + self.matrix[labels[idx].city][self.depot],
+                    idx,
+                )
+            })
+            .min()?;
+
+        let mut path = vec![self.depot];
+        while idx != usize::MAX {
+            path.push(labels[idx].city);
+            idx = labels[idx].parent;
+        }
+
+        path.reverse();
+        Some((cost, path))
+    }
+
+    /// Distance plus a heavy charge for lateness, so feasible tours always win.
+    fn penalised(&self, path: &[usize]) -> u64 {
+        tour::cost(&self.matrix, path) as u64 + LATENESS_PENALTY * self.lateness(path) as u64
+    }
+
+    fn close(&self, order: &[usize]) -> Vec<usize> {
+        let mut path = Vec::with_capacity(order.len() + 2);
+        path.push(self.depot);
+        path.extend_from_slice(order);
+        path.push(self.depot);
+        path
+    }
+
+    /// Permutation GA on the penalised distance, seeded with the tour sorted by closing
+    /// time, whose best tour is finished with local search.
+    fn genetic(&self) -> Vec<usize> {
+        let mut rng = rand::rng();
+        let n = self.matrix.len();
+
+        let mut by_deadline: Vec<usize> = (0..n).filter(|&city| city != self.depot).collect();
+        by_deadline.sort_by_key(|&city| (self.windows[city].close, self.windows[city].open));
+
+        let mut population: Vec<(u64, Vec<usize>)> = (0..self.population.max(ELITISM + 1))
+            .map(|idx| {
+                let mut order = by_deadline.clone();
+
+                if idx > 0 {
+                    order.shuffle(&mut rng);
+                }
+
+                (self.penalised(&self.close(&order)), order)
+            })
+            .collect();
+
+        population.sort();
+
+        for generation in 0..self.generations {
+            let mut next: Vec<(u64, Vec<usize>)> = population[..ELITISM].to_vec();
+
+            while next.len() < population.len() {
+                let parent_1 = tournament(&population, &mut rng);
+                let parent_2 = tournament(&population, &mut rng);
+
+                let mut order = if rng.random::<f64>() < CROSSOVER_RATE {
+                    tour::order_crossover(parent_1, parent_2, &mut rng)
+                } else {
+                    parent_1.to_vec()
+                };
+
+                // Moving one city keeps the rest of the schedule intact, unlike a reversal.
+                if rng.random::<f64>() < MUTATION_RATE && order.len() > 1 {
+                    let city = order.remove(rng.random_range(0..order.len()));
+                    order.insert(rng.random_range(0..=order.len()), city);
+                }
+
+                next.push((self.penalised(&self.close(&order)), order));
+            }
+
+            next.sort();
+            population = next;
+
+            if generation % 100 == 0 {
+                println!("GENERATION {generation}: {}", population[0].0);
+            }
+        }
+
+        let path = self.relocate(self.close(&population[0].1));
+        let late = self.lateness(&path);
+
+        if late > 0 {
+            println!("TIME WINDOWS: no feasible tour found, {late} late in total");
+        }
+
+        path
+    }
+
+    /// First-improvement local search that moves single cities to other positions, judged
+    /// on the penalised distance.
+    fn relocate(&self, mut path: Vec<usize>) -> Vec<usize> {
+        let mut best = self.penalised(&path);
+        let len = path.len();
+        let mut improved = true;
+
+        while improved {
+            improved = false;
+
+            for from in 1..len - 1 {
+                for to in 1..len - 1 {
+                    if from == to {
+                        continue;
+                    }
+
+                    let city = path.remove(from);
+                    path.insert(to, city);
+
+                    let cost = self.penalised(&path);
+
+                    if cost < best {
+                        best = cost;
+                        improved = true;
+                    } else {
+                        let city = path.remove(to);
+                        path.insert(from, city);
+                    }
+                }
+            }
+        }
+
+        path
+    }
+}
+
+fn tournament<'a>(population: &'a [(u64, Vec<usize>)], rng: &mut impl Rng) -> &'a [usize] {
+    &(0..TOURNAMENT)
+        .map(|_| &population[rng.random_range(0..population.len())])
+        .min_by_key(|individual| individual.0)
+        .unwrap()
+        .1
+}
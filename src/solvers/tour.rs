@@ -0,0 +1,243 @@
+use rand::Rng;
+
+pub fn cost(matrix: &[Vec<u32>], tour: &[usize]) -> u32 {
+    tour.windows(2)
+        .map(|current| matrix[current[0]][current[1]])
+        .sum()
+}
+
+/// Whether every distance is the same in both directions. Moves that reverse part of a
+/// tour only keep their cost on symmetric instances.
+pub fn is_symmetric(matrix: &[Vec<u32>]) -> bool {
+    matrix
+        .iter()
+        .enumerate()
+        .all(|(i, row)| row.iter().enumerate().all(|(j, &d)| d == matrix[j][i]))
+}
+
+/// Prefix sums of how much each edge of `path` changes when walked backwards, so reversing
+/// `path[i..=j]` changes the cost of its inner edges by `skew[j] - skew[i]`. All zero on
+/// symmetric instances.
+pub fn reversal_skew(matrix: &[Vec<u32>], path: &[usize]) -> Vec<i64> {
+    let mut skew = Vec::with_capacity(path.len());
+    let mut total = 0;
+
+    skew.push(total);
+    path.windows(2).for_each(|pair| {
+        total += matrix[pair[1]][pair[0]] as i64 - matrix[pair[0]][pair[1]] as i64;
+        skew.push(total);
+    });
+
+    skew
+}
+
+pub fn nearest_neighbour(matrix: &[Vec<u32>], start: usize) -> Vec<usize> {
+    let len = matrix.len();
+    let mut visited = vec![false; len];
+    let mut path = Vec::with_capacity(len + 1);
+    let mut current = start;
+
+    visited[current] = true;
+    path.push(current);
+
+    while path.len() < len {
+        let next = (0..len)
+            .filter(|&city| !visited[city])
+            .min_by_key(|&city| matrix[current][city])
+            .unwrap();
+
+        visited[next] = true;
+        path.push(next);
+        current = next;
+    }
+
+    path.push(start);
+    path
+}
+
+/// Order crossover on any permutation: keeps a random slice of `donor` in place and fills
+/// the rest in the order the remaining elements appear in `other`.
+pub fn order_crossover(donor: &[usize], other: &[usize], rng: &mut impl Rng) -> Vec<usize> {
+    let len = donor.len();
+
+    if len == 0 {
+        return vec![];
+    }
+
+    let (a, b) = (rng.random_range(0..len), rng.random_range(0..len));
+    let (i, j) = (a.min(b), a.max(b));
+
+    let mut taken = vec![false; donor.iter().max().map_or(0, |&max| max + 1)];
+    donor[i..=j].iter().for_each(|&item| taken[item] = true);
+
+    let mut rest = other.iter().filter(|&&item| !taken[item]);
+    let mut child = donor.to_vec();
+
+    (0..len)
+        .filter(|&idx| idx < i || idx > j)
+        .for_each(|idx| child[idx] = *rest.next().unwrap());
+
+    child
+}
+
+pub fn candidate_lists(matrix: &[Vec<u32>], k: usize) -> Vec<Vec<usize>> {
+    let len = matrix.len();
+
+    (0..len)
+        .map(|city| {
+            let mut others: Vec<usize> = (0..len).filter(|&other| other != city).collect();
+            others.sort_by_key(|&other| matrix[city][other]);
+            others.truncate(k);
+            others
+        })
+        .collect()
+}
+
+/// Array representation of a cyclic tour. Reversals flip whichever side of the cycle is
+/// shorter, so the logical orientation may run against the stored order.
+pub struct Ring {
+    order: Vec<usize>,
+    pos: Vec<usize>,
+    flipped: bool,
+}
+
+impl Ring {
+    pub fn new(order: Vec<usize>, cities: usize) -> Self {
+        let mut pos = vec![usize::MAX; cities];
+        order
+            .iter()
+            .enumerate()
+            .for_each(|(idx, &city)| pos[city] = idx);
+
+        Self {
+            order,
+            pos,
+            flipped: false,
+        }
+    }
+
+    pub fn cities(&self) -> &[usize] {
+        &self.order
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn contains(&self, city: usize) -> bool {
+        self.pos[city] != usize::MAX
+    }
+
+    pub fn next(&self, city: usize) -> usize {
+        self.step(city, true)
+    }
+
+    pub fn prev(&self, city: usize) -> usize {
+        self.step(city, false)
+    }
+
+    pub fn step(&self, city: usize, forward: bool) -> usize {
+        let len = self.len();
+
+        if forward != self.flipped {
+            self.order[(self.pos[city] + 1) % len]
+        } else {
+            self.order[(self.pos[city] + len - 1) % len]
+        }
+    }
+
+    pub fn offset(&self, from: usize, to: usize) -> usize {
+        let len = self.len();
+
+        if self.flipped {
+            (self.pos[from] + len - self.pos[to]) % len
+        } else {
+            (self.pos[to] + len - self.pos[from]) % len
+        }
+    }
+
+    /// Reverses the path walking forward from `from` to `to`.
+    pub fn reverse(&mut self, from: usize, to: usize) {
+        let inner = self.offset(from, to) + 1;
+
+        if inner * 2 <= self.len() {
+            self.reverse_stored(from, to, inner);
+        } else {
+            let (outer_from, outer_to) = (self.next(to), self.prev(from));
+            let outer = self.len() - inner;
+
+            if outer > 0 {
+                self.reverse_stored(outer_from, outer_to, outer);
+            }
+
+            self.flipped = !self.flipped;
+        }
+    }
+
+    fn reverse_stored(&mut self, from: usize, to: usize, count: usize) {
+        let len = self.len();
+        let (mut i, mut j) = if self.flipped {
+            (self.pos[to], self.pos[from])
+        } else {
+            (self.pos[from], self.pos[to])
+        };
+
+        for _ in 0..count / 2 {
+            self.order.swap(i, j);
+            self.pos[self.order[i]] = i;
+            self.pos[self.order[j]] = j;
+
+            i = (i + 1) % len;
+            j = (j + len - 1) % len;
+        }
+    }
+
+    /// Moves the segment `first..last` between `after` and its successor.
+    pub fn move_segment(&mut self, first: usize, last: usize, after: usize, reversed: bool) {
+        let mut segment = vec![first];
+        while *segment.last().unwrap() != last {
+            segment.push(self.next(*segment.last().unwrap()));
+        }
+
+        if reversed {
+            segment.reverse();
+        }
+
+        let mut rebuilt = Vec::with_capacity(self.len());
+        let mut city = self.next(last);
+
+        while city != first {
+            rebuilt.push(city);
+
+            if city == after {
+                rebuilt.extend_from_slice(&segment);
+            }
+
+            city = self.next(city);
+        }
+
+        rebuilt
+            .iter()
+            .enumerate()
+            .for_each(|(idx, &city)| self.pos[city] = idx);
+        self.order = rebuilt;
+        self.flipped = false;
+    }
+
+    pub fn closed_from(&self, start: usize) -> Vec<usize> {
+        let mut closed = Vec::with_capacity(self.len() + 1);
+        let mut city = start;
+
+        loop {
+            closed.push(city);
+            city = self.next(city);
+
+            if city == start {
+                break;
+            }
+        }
+
+        closed.push(start);
+        closed
+    }
+}